@@ -1,2 +1,3 @@
+pub mod prelude;
 pub mod str;
 pub mod util;
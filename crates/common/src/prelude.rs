@@ -0,0 +1,9 @@
+//! Re-exports of commonly used types and functions.
+//!
+//! ```
+//! use common::prelude::*;
+//! ```
+
+pub use crate::str::{Ascii, AsciiError, Utf16, Utf16DecodeError, Utf16Error};
+pub use crate::util::crc::{crc16, crc32};
+pub use crate::util::FileSize;
@@ -1,4 +1,6 @@
 use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::error::Error;
 use std::fmt;
 use std::ops::Deref;
 use std::str;
@@ -22,6 +24,8 @@ impl fmt::Display for AsciiError {
     }
 }
 
+impl Error for AsciiError {}
+
 /// An ASCII string with a fixed capacity, `N`.
 ///
 /// Terminated by `0x00` byte.
@@ -97,6 +101,14 @@ fn validate_ascii(bytes: &[u8]) -> Result<&str, AsciiError> {
     }
 }
 
+impl<const N: usize> Default for Ascii<N> {
+    /// Creates an empty string.
+    #[inline]
+    fn default() -> Self {
+        Ascii { buf: [0; N] }
+    }
+}
+
 impl<const N: usize> fmt::Debug for Ascii<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.to_string_lossy().fmt(f)
@@ -117,6 +129,21 @@ impl<const N: usize, const M: usize> PartialEq<Ascii<M>> for Ascii<N> {
     }
 }
 
+impl<const N: usize, const M: usize> PartialOrd<Ascii<M>> for Ascii<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Ascii<M>) -> Option<Ordering> {
+        Some(self.as_bytes().cmp(other.as_bytes()))
+    }
+}
+
+impl<const N: usize> Ord for Ascii<N> {
+    /// Compares the string content lexicographically by bytes.
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
 impl<const N: usize> PartialEq<str> for Ascii<N> {
     fn eq(&self, other: &str) -> bool {
         self.buf[..self.len()] == *other.as_bytes()
@@ -1,5 +1,7 @@
 mod ascii;
+#[cfg(feature = "serde")]
+mod serde;
 mod utf16;
 
 pub use self::ascii::{Ascii, AsciiError};
-pub use self::utf16::Utf16;
+pub use self::utf16::{Utf16, Utf16DecodeError, Utf16Error};
@@ -0,0 +1,84 @@
+//! Lossless `serde` support for the fixed capacity strings.
+//!
+//! The strings are serialized as the raw array of `N` code units, including
+//! any padding after the terminator, so that a round-trip reproduces the exact
+//! buffer.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use ::serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use ::serde::ser::{Serialize, SerializeTuple, Serializer};
+
+use super::{Ascii, Utf16};
+
+impl<const N: usize> Serialize for Ascii<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_array(&self.buf, serializer)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Ascii<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let buf = deserialize_array(deserializer)?;
+        Ok(Ascii { buf })
+    }
+}
+
+impl<const N: usize> Serialize for Utf16<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_array(&<[u16; N]>::from(*self), serializer)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Utf16<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let units: [u16; N] = deserialize_array(deserializer)?;
+        Ok(Utf16::from(units))
+    }
+}
+
+fn serialize_array<T: Serialize, S: Serializer>(
+    array: &[T],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut tuple = serializer.serialize_tuple(array.len())?;
+    for element in array {
+        tuple.serialize_element(element)?;
+    }
+    tuple.end()
+}
+
+fn deserialize_array<'de, T, D, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+where
+    T: Deserialize<'de> + Copy + Default,
+    D: Deserializer<'de>,
+{
+    struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+    impl<'de, T, const N: usize> Visitor<'de> for ArrayVisitor<T, N>
+    where
+        T: Deserialize<'de> + Copy + Default,
+    {
+        type Value = [T; N];
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "an array of length {}", N)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut array = [T::default(); N];
+            for (i, element) in array.iter_mut().enumerate() {
+                *element = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+            }
+            if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                return Err(de::Error::invalid_length(N + 1, &self));
+            }
+            Ok(array)
+        }
+    }
+
+    deserializer.deserialize_tuple(N, ArrayVisitor(PhantomData))
+}
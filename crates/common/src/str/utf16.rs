@@ -1,9 +1,72 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
 use std::string::FromUtf16Error;
 
+/// An error encoding a string which does not fit in a [`Utf16`] string.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Utf16Error {
+    len: usize,
+    capacity: usize,
+}
+
+impl Utf16Error {
+    /// Returns the length of the encoded string in code units.
+    pub fn encoded_len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the capacity of the string in code units.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl fmt::Display for Utf16Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "string of {} code units exceeds capacity of {}",
+            self.len, self.capacity
+        )
+    }
+}
+
+impl Error for Utf16Error {}
+
+/// An error decoding a [`Utf16`] string containing unpaired surrogates.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Utf16DecodeError {
+    valid_up_to: usize,
+}
+
+impl Utf16DecodeError {
+    /// Returns the index in code units up to which the string is valid.
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+}
+
+impl fmt::Display for Utf16DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid UTF-16 at index {}: unpaired surrogate",
+            self.valid_up_to
+        )
+    }
+}
+
+impl Error for Utf16DecodeError {}
+
 /// A UTF-16 string with a fixed capacity, `N`.
 ///
 /// Terminated by `0x0000`.
+///
+/// The code units are stored little-endian, as in the ROM, regardless of the
+/// host endianness.
 #[derive(Clone, Copy, Eq)]
 #[repr(transparent)]
 pub struct Utf16<const N: usize> {
@@ -11,17 +74,40 @@ pub struct Utf16<const N: usize> {
 }
 
 impl<const N: usize> Utf16<N> {
+    /// Creates a string from little-endian bytes.
+    ///
+    /// The string is truncated to `N` code units, or padded with `0x0000`.
+    pub fn from_le_bytes(bytes: &[u8]) -> Utf16<N> {
+        let mut chars = [0u16; N];
+        for (c, b) in chars.iter_mut().zip(bytes.chunks_exact(2)) {
+            // Keep the little-endian representation in memory.
+            *c = u16::from_ne_bytes([b[0], b[1]]);
+        }
+
+        Utf16 { chars }
+    }
+
     /// Returns the length of the string.
     pub fn len(&self) -> usize {
         self.chars.iter().position(|&c| c == 0).unwrap_or(N)
     }
 
+    /// Returns the code units of the string, in host endianness.
+    pub fn units(&self) -> Cow<'_, [u16]> {
+        to_host(&self.chars[..self.len()])
+    }
+
+    /// Returns an iterator over the code units of the string, in host
+    /// endianness, split by the separator `sep`.
+    pub fn split(&self, sep: u16) -> impl Iterator<Item = Cow<'_, [u16]>> + '_ {
+        self.chars[..self.len()]
+            .split(move |&c| u16::from_le(c) == sep)
+            .map(to_host)
+    }
+
     /// Returns the string content.
     pub fn to_string(&self) -> Result<String, FromUtf16Error> {
-        let len = self.len();
-        let chars = &self.chars[..len];
-
-        String::from_utf16(chars)
+        String::from_utf16(&self.units())
     }
 
     /// Returns the string content, with invalid characters replaced by
@@ -29,10 +115,82 @@ impl<const N: usize> Utf16<N> {
     ///
     /// [U+FFFD]: char::REPLACEMENT_CHARACTER
     pub fn to_string_lossy(&self) -> String {
-        let len = self.len();
-        let chars = &self.chars[..len];
+        String::from_utf16_lossy(&self.units())
+    }
 
-        String::from_utf16_lossy(chars)
+    /// Returns the string content, or an error with the index of the first
+    /// unpaired surrogate.
+    pub fn decode(&self) -> Result<String, Utf16DecodeError> {
+        match self.decode_lossy() {
+            (s, None) => Ok(s),
+            (_, Some(err)) => Err(err),
+        }
+    }
+
+    /// Returns the string content, with each unpaired surrogate replaced by
+    /// [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD], along with the error for the
+    /// first unpaired surrogate, if any.
+    ///
+    /// [U+FFFD]: char::REPLACEMENT_CHARACTER
+    pub fn decode_lossy(&self) -> (String, Option<Utf16DecodeError>) {
+        let units = self.units();
+
+        let mut res = String::with_capacity(units.len());
+        let mut error = None;
+        let mut index = 0;
+        for c in char::decode_utf16(units.iter().copied()) {
+            match c {
+                Ok(c) => {
+                    res.push(c);
+                    index += c.len_utf16();
+                }
+                Err(_) => {
+                    res.push(char::REPLACEMENT_CHARACTER);
+                    error.get_or_insert(Utf16DecodeError { valid_up_to: index });
+                    index += 1;
+                }
+            }
+        }
+
+        (res, error)
+    }
+}
+
+/// Converts little-endian code units to host endianness.
+fn to_host(units: &[u16]) -> Cow<'_, [u16]> {
+    if cfg!(target_endian = "little") {
+        Cow::Borrowed(units)
+    } else {
+        Cow::Owned(units.iter().map(|&c| u16::from_le(c)).collect())
+    }
+}
+
+impl<const N: usize> Default for Utf16<N> {
+    /// Creates an empty string.
+    #[inline]
+    fn default() -> Self {
+        Utf16 { chars: [0; N] }
+    }
+}
+
+impl<const N: usize> FromStr for Utf16<N> {
+    type Err = Utf16Error;
+
+    /// Encodes a string, padded with `0x0000`.
+    ///
+    /// The string is not terminated if it is exactly `N` code units.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let len = s.encode_utf16().count();
+        if len > N {
+            return Err(Utf16Error { len, capacity: N });
+        }
+
+        let mut chars = [0u16; N];
+        for (c, unit) in chars.iter_mut().zip(s.encode_utf16()) {
+            *c = unit.to_le();
+        }
+
+        Ok(Utf16 { chars })
     }
 }
 
@@ -50,8 +208,18 @@ impl<const N: usize> fmt::Display for Utf16<N> {
 
 impl<const N: usize> From<Utf16<N>> for [u16; N] {
     #[inline]
-    fn from(ascii: Utf16<N>) -> Self {
-        ascii.chars
+    fn from(s: Utf16<N>) -> Self {
+        s.chars.map(u16::from_le)
+    }
+}
+
+impl<const N: usize> From<[u16; N]> for Utf16<N> {
+    /// Creates a string from code units in host endianness.
+    #[inline]
+    fn from(units: [u16; N]) -> Self {
+        Utf16 {
+            chars: units.map(u16::to_le),
+        }
     }
 }
 
@@ -64,6 +232,21 @@ impl<const N: usize, const M: usize> PartialEq<Utf16<M>> for Utf16<N> {
     }
 }
 
+impl<const N: usize, const M: usize> PartialOrd<Utf16<M>> for Utf16<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Utf16<M>) -> Option<Ordering> {
+        Some(self.units().cmp(&other.units()))
+    }
+}
+
+impl<const N: usize> Ord for Utf16<N> {
+    /// Compares the string content lexicographically by code units.
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.units().cmp(&other.units())
+    }
+}
+
 impl<const N: usize> PartialEq<str> for Utf16<N> {
     fn eq(&self, other: &str) -> bool {
         match self.to_string() {
@@ -81,9 +81,40 @@ pub fn crc16(bytes: &[u8]) -> u16 {
 
 /// Cyclic redundancy check.
 pub fn crc32(bytes: &[u8]) -> u32 {
-    let mut crc = 0xFFFFFFFF;
-    for &b in bytes {
-        crc = (crc >> 8) ^ CRC32_TABLE[((crc ^ (b as u32)) & 0xFF) as usize];
+    let mut crc = Crc32::new();
+    crc.update(bytes);
+    crc.finish()
+}
+
+/// Streaming [`crc32`], for data processed in chunks.
+#[derive(Clone, Copy, Debug)]
+pub struct Crc32 {
+    crc: u32,
+}
+
+impl Crc32 {
+    /// Creates a new CRC state.
+    pub fn new() -> Crc32 {
+        Crc32 { crc: 0xFFFFFFFF }
+    }
+
+    /// Processes more input data.
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.crc = (self.crc >> 8) ^ CRC32_TABLE[((self.crc ^ (b as u32)) & 0xFF) as usize];
+        }
+    }
+
+    /// Returns the CRC of the data processed so far.
+    ///
+    /// Like [`crc32`], the final value is not inverted.
+    pub fn finish(&self) -> u32 {
+        self.crc
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Crc32 {
+        Crc32::new()
     }
-    crc
 }
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::ops::{Add, Sub};
 
 // Though not technically correct, follow convention and use decimal scale
 // notation with a binary divisor.
@@ -6,9 +7,43 @@ const DIVISOR: f64 = 1024.0;
 static SCALE: [&str; 9] = ["B", "KB", "MB", "GB", "TB", "PB", "EB", "ZB", "YB"];
 
 /// A format utility for human readable file sizes.
-#[derive(Clone, Copy, Debug)]
+///
+/// Sizes are compared and sorted by their number of bytes.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct FileSize(pub usize);
 
+impl From<usize> for FileSize {
+    #[inline]
+    fn from(size: usize) -> Self {
+        FileSize(size)
+    }
+}
+
+impl From<FileSize> for usize {
+    #[inline]
+    fn from(size: FileSize) -> Self {
+        size.0
+    }
+}
+
+impl Add for FileSize {
+    type Output = FileSize;
+
+    #[inline]
+    fn add(self, rhs: FileSize) -> FileSize {
+        FileSize(self.0 + rhs.0)
+    }
+}
+
+impl Sub for FileSize {
+    type Output = FileSize;
+
+    #[inline]
+    fn sub(self, rhs: FileSize) -> FileSize {
+        FileSize(self.0 - rhs.0)
+    }
+}
+
 impl fmt::Display for FileSize {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut size = self.0 as f64;
@@ -1,5 +1,7 @@
 mod file_size;
 
 pub mod crc;
+pub mod md5;
+pub mod sha1;
 
 pub use self::file_size::FileSize;
@@ -0,0 +1,121 @@
+//! SHA-1 message digest.
+//!
+//! \[1\]: <https://datatracker.ietf.org/doc/html/rfc3174>
+
+/// Computes the SHA-1 digest of `bytes`.
+pub fn sha1(bytes: &[u8]) -> [u8; 20] {
+    let mut sha1 = Sha1::new();
+    sha1.update(bytes);
+    sha1.finish()
+}
+
+/// Streaming SHA-1, for data processed in chunks.
+#[derive(Clone, Debug)]
+pub struct Sha1 {
+    state: [u32; 5],
+    block: [u8; 64],
+    block_len: usize,
+    len: u64,
+}
+
+impl Sha1 {
+    /// Creates a new SHA-1 state.
+    pub fn new() -> Sha1 {
+        Sha1 {
+            state: [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0],
+            block: [0; 64],
+            block_len: 0,
+            len: 0,
+        }
+    }
+
+    /// Processes more input data.
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        self.len = self.len.wrapping_add(bytes.len() as u64);
+
+        if self.block_len > 0 {
+            let n = bytes.len().min(64 - self.block_len);
+            self.block[self.block_len..(self.block_len + n)].copy_from_slice(&bytes[..n]);
+            self.block_len += n;
+            bytes = &bytes[n..];
+
+            if self.block_len < 64 {
+                return;
+            }
+            let block = self.block;
+            self.compress(&block);
+            self.block_len = 0;
+        }
+
+        let mut blocks = bytes.chunks_exact(64);
+        for block in &mut blocks {
+            self.compress(block.try_into().unwrap());
+        }
+
+        let rest = blocks.remainder();
+        self.block[..rest.len()].copy_from_slice(rest);
+        self.block_len = rest.len();
+    }
+
+    /// Returns the digest of the data processed so far.
+    pub fn finish(&self) -> [u8; 20] {
+        let mut sha1 = self.clone();
+        let bit_len = self.len.wrapping_mul(8);
+
+        // Padding is a single set bit, then zeros up to the length in the last
+        // 8 bytes of a block.
+        sha1.update(&[0x80]);
+        while sha1.block_len != 56 {
+            sha1.update(&[0]);
+        }
+        sha1.update(&bit_len.to_be_bytes());
+
+        let mut digest = [0; 20];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(sha1.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+
+    fn compress(&mut self, block: &[u8; 64]) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes(word.try_into().unwrap());
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = self.state;
+        for (i, &w) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(w);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (state, x) in self.state.iter_mut().zip([a, b, c, d, e]) {
+            *state = state.wrapping_add(x);
+        }
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Sha1 {
+        Sha1::new()
+    }
+}
@@ -0,0 +1,35 @@
+use common::util::FileSize;
+
+const KB: usize = 1024;
+const MB: usize = 1024 * KB;
+
+#[test]
+fn ordering() {
+    assert!(FileSize(1023) < FileSize(KB));
+    assert!(FileSize(KB) < FileSize(KB + 1));
+    assert!(FileSize(MB - 1) < FileSize(MB));
+    assert!(FileSize(256 * MB) > FileSize(128 * MB));
+    assert_eq!(FileSize(MB), FileSize::from(1024 * KB));
+
+    let mut sizes = [FileSize(MB), FileSize(512), FileSize(64 * KB), FileSize(0)];
+    sizes.sort();
+    assert_eq!(
+        sizes,
+        [FileSize(0), FileSize(512), FileSize(64 * KB), FileSize(MB)]
+    );
+}
+
+#[test]
+fn arithmetic() {
+    assert_eq!(FileSize(MB) + FileSize(MB), FileSize(2 * MB));
+    assert_eq!(FileSize(MB) - FileSize(512 * KB), FileSize(512 * KB));
+    assert_eq!(usize::from(FileSize(KB)), KB);
+}
+
+#[test]
+fn display() {
+    assert_eq!(FileSize(512).to_string(), "512 B");
+    assert_eq!(FileSize(KB).to_string(), "1 KB");
+    assert_eq!(FileSize(1536).to_string(), "1.50 KB");
+    assert_eq!(FileSize(128 * MB).to_string(), "128 MB");
+}
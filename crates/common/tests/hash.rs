@@ -0,0 +1,55 @@
+use common::util::crc::{crc32, Crc32};
+use common::util::md5::{md5, Md5};
+use common::util::sha1::{sha1, Sha1};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[test]
+fn sha1_known_answers() {
+    assert_eq!(hex(&sha1(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+    assert_eq!(
+        hex(&sha1(b"abc")),
+        "a9993e364706816aba3e25717850c26c9cd0d89d"
+    );
+    assert_eq!(
+        hex(&sha1(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        )),
+        "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+    );
+    assert_eq!(
+        hex(&sha1(&[b'a'; 1_000_000])),
+        "34aa973cd4c4daa4f61eeb2bdbad27316534016f"
+    );
+}
+
+#[test]
+fn md5_known_answers() {
+    assert_eq!(hex(&md5(b"")), "d41d8cd98f00b204e9800998ecf8427e");
+    assert_eq!(hex(&md5(b"abc")), "900150983cd24fb0d6963f7d28e17f72");
+    assert_eq!(
+        hex(&md5(
+            b"12345678901234567890123456789012345678901234567890123456789012345678901234567890"
+        )),
+        "57edf4a22be3c955ac49da2e2107b67a"
+    );
+}
+
+#[test]
+fn streaming() {
+    let data = (0..1000u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+
+    let mut sha = Sha1::new();
+    let mut md = Md5::new();
+    let mut crc = Crc32::new();
+    for chunk in data.chunks(37) {
+        sha.update(chunk);
+        md.update(chunk);
+        crc.update(chunk);
+    }
+    assert_eq!(sha.finish(), sha1(&data));
+    assert_eq!(md.finish(), md5(&data));
+    assert_eq!(crc.finish(), crc32(&data));
+}
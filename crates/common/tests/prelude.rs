@@ -0,0 +1,10 @@
+use common::prelude::*;
+
+#[test]
+fn prelude() {
+    assert_eq!(crc16(b"123456789"), 0x4B37);
+    assert_eq!(crc32(b"123456789"), 0x340BC6D9);
+    assert_eq!(FileSize(1024).to_string(), "1 KB");
+    assert_eq!(Ascii::<4>::default().len(), 0);
+    assert_eq!(Utf16::<4>::default().len(), 0);
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "serde")]
+
+use common::str::{Ascii, Utf16};
+
+#[test]
+fn ascii_round_trip() {
+    // Padding after the terminator is preserved.
+    let s = Ascii::<6> {
+        buf: *b"AB\0\xFF\xFF\xFF",
+    };
+
+    let json = serde_json::to_string(&s).unwrap();
+    assert_eq!(json, "[65,66,0,255,255,255]");
+
+    let de: Ascii<6> = serde_json::from_str(&json).unwrap();
+    assert_eq!(de.buf, s.buf);
+}
+
+#[test]
+fn utf16_round_trip() {
+    let s = Utf16::<4>::from([0x61, 0x0000, 0xFFFF, 0xFFFF]);
+
+    let json = serde_json::to_string(&s).unwrap();
+    assert_eq!(json, "[97,0,65535,65535]");
+
+    let de: Utf16<4> = serde_json::from_str(&json).unwrap();
+    assert_eq!(<[u16; 4]>::from(de), [0x61, 0x0000, 0xFFFF, 0xFFFF]);
+}
+
+#[test]
+fn invalid_length() {
+    assert!(serde_json::from_str::<Ascii<2>>("[65]").is_err());
+    assert!(serde_json::from_str::<Ascii<2>>("[65,66,67]").is_err());
+    assert!(serde_json::from_str::<Utf16<2>>("[65,66,67]").is_err());
+}
@@ -0,0 +1,72 @@
+use common::str::{Ascii, Utf16};
+
+#[test]
+fn utf16_from_le_bytes() {
+    let s =
+        Utf16::<4>::from_le_bytes(&[b'a', 0x00, 0xE9, 0x00, b'b', 0x00, b'c', 0x00, b'd', 0x00]);
+    assert_eq!(s, "a\u{E9}bc");
+    assert_eq!(<[u16; 4]>::from(s), [0x61, 0xE9, 0x62, 0x63]);
+
+    let s = Utf16::<4>::from_le_bytes(&[b'a', 0x00]);
+    assert_eq!(s.len(), 1);
+    assert_eq!(&*s.units(), &[0x61]);
+}
+
+#[test]
+fn default_strings() {
+    let s = Utf16::<128>::default();
+    assert_eq!(s.len(), 0);
+    assert_eq!(s, "");
+
+    let s = Ascii::<12>::default();
+    assert_eq!(s.len(), 0);
+    assert_eq!(s.buf, [0; 12]);
+}
+
+#[test]
+fn utf16_split() {
+    let s = Utf16::<16>::from_le_bytes(&[b'a', 0, b'b', 0, 0x0A, 0, b'c', 0, 0x0A, 0, 0x0A, 0]);
+    let lines = s.split(0x000A).collect::<Vec<_>>();
+    assert_eq!(lines, [&[0x61, 0x62][..], &[0x63], &[], &[]]);
+
+    // Split units are in host endianness, like `units`.
+    let s = Utf16::<16>::from_le_bytes(&[0x42, 0x30, 0x0A, 0, 0xE9, 0]);
+    let lines = s.split(0x000A).collect::<Vec<_>>();
+    assert_eq!(lines, [&[0x3042][..], &[0x00E9]]);
+
+    let s = Utf16::<16>::default();
+    assert_eq!(s.split(0x000A).collect::<Vec<_>>(), [&[] as &[u16]]);
+}
+
+#[test]
+fn utf16_decode() {
+    let s = Utf16::<8>::from_le_bytes(&[b'a', 0, 0x3D, 0xD8, 0x00, 0xDE, 0x00, 0xD8, b'b', 0]);
+    assert_eq!(s.decode().unwrap_err().valid_up_to(), 3);
+
+    let (lossy, err) = s.decode_lossy();
+    assert_eq!(lossy, "a\u{1F600}\u{FFFD}b");
+    assert_eq!(err.map(|err| err.valid_up_to()), Some(3));
+
+    let s = Utf16::<8>::from_le_bytes(&[b'a', 0, 0x3D, 0xD8, 0x00, 0xDE]);
+    assert_eq!(s.decode(), Ok("a\u{1F600}".to_owned()));
+    assert_eq!(s.decode_lossy(), ("a\u{1F600}".to_owned(), None));
+}
+
+#[test]
+fn string_ordering() {
+    let ascii = |s: &[u8]| {
+        let mut buf = [0; 12];
+        buf[..s.len()].copy_from_slice(s);
+        Ascii::<12> { buf }
+    };
+
+    let mut titles = [ascii(b"ZELDA"), ascii(b"MARIO KART"), ascii(b"MARIO")];
+    titles.sort();
+    assert_eq!(titles, ["MARIO", "MARIO KART", "ZELDA"]);
+    assert!(Ascii::<4> { buf: *b"ABCD" } < ascii(b"ABCE"));
+
+    let mut titles = ["\u{3042}", "b", "a"].map(|s| s.parse::<Utf16<8>>().unwrap());
+    titles.sort();
+    assert_eq!(titles, ["a", "b", "\u{3042}"]);
+    assert!("a".parse::<Utf16<4>>().unwrap() < "ab".parse::<Utf16<8>>().unwrap());
+}
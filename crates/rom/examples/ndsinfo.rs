@@ -4,7 +4,7 @@ use std::io::{self, Write};
 use std::process;
 
 use common::util::FileSize;
-use rom::nds::{NdsBanner, NdsRom};
+use rom::nds::{NdsBanner, NdsHeader, NdsRom};
 
 fn main() -> Result<(), Box<dyn Error>> {
     pretty_env_logger::try_init_custom_env("RSDS_LOG").unwrap();
@@ -33,6 +33,12 @@ fn main() -> Result<(), Box<dyn Error>> {
     if let Some(banner) = &rom.banner {
         writeln!(f)?;
         banner_info(&mut f, banner)?;
+
+        #[cfg(feature = "ansi")]
+        {
+            writeln!(f)?;
+            write!(f, "{}", banner.icon_ansi())?;
+        }
     }
 
     f.flush()?;
@@ -44,103 +50,121 @@ fn main() -> Result<(), Box<dyn Error>> {
 fn header_info<W: io::Write>(mut f: W, rom: &NdsRom) -> io::Result<()> {
     let header = &rom.header;
 
+    let field = |name| {
+        NdsHeader::field_map()
+            .iter()
+            .find(|&&(_, field, _)| field == name)
+            .map(|&(offset, _, size)| (offset, size))
+            .unwrap()
+    };
+
     macro_rules! w {
-        ($addr:literal, $name:literal) => {
-            write!(f, concat!($addr, "  ", $name))
-        };
-        ($addr:literal, $name:expr, $fmt:literal, $($value:expr),*) => {
-            write!(f, concat!($addr, "  {:40}  ", $fmt), $name, $($value),*)
+        ($field:ident, $name:literal) => {{
+            let (offset, size) = field(stringify!($field));
+            let unit = if size == 1 { "byte" } else { "bytes" };
+            write!(f, "{:#05X}  {} ({} {})\n", offset, $name, size, unit)
+        }};
+        ($field:ident, $name:expr, $fmt:literal, $($value:expr),*) => {
+            write!(f, concat!("{:#05X}  {:40}  ", $fmt), field(stringify!($field)).0, $name, $($value),*)
         };
     }
 
     writeln!(f, "Header information:")?;
 
-    w!("0x000", "Game title", "{}\n", header.game_title)?;
+    w!(game_title, "Game title", "{}\n", header.game_title)?;
 
-    w!("0x00C", "Game code", "{}", header.game_code)?;
+    w!(game_code, "Game code", "{}", header.game_code)?;
     if let Some(region) = header.region() {
         write!(f, " (NTR-{}-{})", header.game_code, region)?;
     }
     writeln!(f)?;
 
-    w!("0x010", "Maker code", "{}", header.maker_code)?;
+    w!(maker_code, "Maker code", "{}", header.maker_code)?;
     if let Some(maker) = header.maker() {
         write!(f, " ({})", maker)?;
     }
     writeln!(f)?;
 
-    w!("0x012", "Unit code", "{:#04X}\n", header.unit_code)?;
-    w!("0x013", "Device type", "{:#04X}\n", header.device_type)?;
-    w!("0x014", "Device capacity", "{:#04X} ({})\n", header.device_capacity, FileSize(header.device_capacity_bytes()))?;
-    w!("0x015", "(8 bytes reserved)\n")?;
-    w!("0x01D", "NDS region", "{:#04X}\n", header.nds_region)?;
-    w!("0x01E", "ROM version", "{:#04X}\n", header.rom_version)?;
-    w!("0x01F", "Autostart", "{:#04X}\n", header.autostart)?;
-
-    w!("0x020", "ARM9 ROM offset", "{:#X}\n", header.arm9_rom_offset)?;
-    w!("0x024", "ARM9 entry address", "{:#X}\n", header.arm9_entry_address)?;
-    w!("0x028", "ARM9 RAM address", "{:#X}\n", header.arm9_ram_address)?;
-    w!("0x02C", "ARM9 code size", "{:#X}\n", header.arm9_size)?;
-
-    w!("0x030", "ARM7 ROM offset", "{:#X}\n", header.arm7_rom_offset)?;
-    w!("0x034", "ARM7 entry address", "{:#X}\n", header.arm7_entry_address)?;
-    w!("0x038", "ARM7 RAM address", "{:#X}\n", header.arm7_ram_address)?;
-    w!("0x03C", "ARM7 code size", "{:#X}\n", header.arm7_size)?;
-
-    w!("0x040", "File name table (FNT) offset", "{:#X}\n", header.fnt_offset)?;
-    w!("0x044", "File name table (FNT) size", "{:#X}\n", header.fnt_size)?;
-    w!("0x048", "File allocation table (FAT) offset", "{:#X}\n", header.fat_offset)?;
-    w!("0x04C", "File allocation table (FAT) size", "{:#X}\n", header.fat_size)?;
-
-    w!("0x050", "ARM9 overlay offset", "{:#X}\n", header.arm9_overlay_offset)?;
-    w!("0x054", "ARM9 overlay size", "{:#X}\n", header.arm9_overlay_size)?;
-    w!("0x058", "ARM7 overlay offset", "{:#X}\n", header.arm7_overlay_offset)?;
-    w!("0x05C", "ARM7 overlay size", "{:#X}\n", header.arm7_overlay_size)?;
-
-    w!("0x060", "Normal commands settings", "{:#010X}\n", header.normal_command_settings)?;
-    w!("0x064", "KEY1 commands settings", "{:#010X}\n", header.key1_command_settings)?;
-
-    w!("0x068", "Banner offset", "{:#X}\n", header.banner_offset)?;
+    w!(unit_code, "Unit code", "{:#04X}\n", header.unit_code)?;
+    w!(device_type, "Device type", "{:#04X}\n", header.device_type)?;
+    w!(device_capacity, "Device capacity", "{:#04X}", header.device_capacity)?;
+    if let Some(size) = header
+        .checked_device_capacity_bytes()
+        .and_then(|size| usize::try_from(size).ok())
+    {
+        write!(f, " ({})", FileSize(size))?;
+    }
+    writeln!(f)?;
+    w!(reserved1, "Reserved")?;
+    let dsi_flags = header.get_u8(field("dsi_flags").0).unwrap();
+    w!(dsi_flags, "DSi flags", "{:#04X}\n", dsi_flags)?;
+    w!(nds_region, "NDS region", "{:#04X}\n", header.nds_region)?;
+    w!(rom_version, "ROM version", "{:#04X}\n", header.rom_version)?;
+    w!(autostart, "Autostart", "{:#04X}\n", header.autostart)?;
+
+    w!(arm9_rom_offset, "ARM9 ROM offset", "{:#X}\n", header.arm9_rom_offset)?;
+    w!(arm9_entry_address, "ARM9 entry address", "{:#X}\n", header.arm9_entry_address)?;
+    w!(arm9_ram_address, "ARM9 RAM address", "{:#X}\n", header.arm9_ram_address)?;
+    w!(arm9_size, "ARM9 code size", "{:#X}\n", header.arm9_size)?;
+
+    w!(arm7_rom_offset, "ARM7 ROM offset", "{:#X}\n", header.arm7_rom_offset)?;
+    w!(arm7_entry_address, "ARM7 entry address", "{:#X}\n", header.arm7_entry_address)?;
+    w!(arm7_ram_address, "ARM7 RAM address", "{:#X}\n", header.arm7_ram_address)?;
+    w!(arm7_size, "ARM7 code size", "{:#X}\n", header.arm7_size)?;
+
+    w!(fnt_offset, "File name table (FNT) offset", "{:#X}\n", header.fnt_offset)?;
+    w!(fnt_size, "File name table (FNT) size", "{:#X}\n", header.fnt_size)?;
+    w!(fat_offset, "File allocation table (FAT) offset", "{:#X}\n", header.fat_offset)?;
+    w!(fat_size, "File allocation table (FAT) size", "{:#X}\n", header.fat_size)?;
+
+    w!(arm9_overlay_offset, "ARM9 overlay offset", "{:#X}\n", header.arm9_overlay_offset)?;
+    w!(arm9_overlay_size, "ARM9 overlay size", "{:#X}\n", header.arm9_overlay_size)?;
+    w!(arm7_overlay_offset, "ARM7 overlay offset", "{:#X}\n", header.arm7_overlay_offset)?;
+    w!(arm7_overlay_size, "ARM7 overlay size", "{:#X}\n", header.arm7_overlay_size)?;
+
+    w!(normal_command_settings, "Normal commands settings", "{:#010X} ({})\n", header.normal_command_settings, header.normal_command_settings())?;
+    w!(key1_command_settings, "KEY1 commands settings", "{:#010X} ({})\n", header.key1_command_settings, header.key1_command_settings())?;
+
+    w!(banner_offset, "Banner offset", "{:#X}\n", header.banner_offset)?;
 
     let secure_area_crc = match rom.compute_secure_area_crc16() {
         Some(crc) if crc == header.secure_area_crc16 => "OK",
         Some(_) => "INVALID",
         None => "-",
     };
-    w!("0x06C", "Secure area CRC", "{:#06X} ({})\n", header.secure_area_crc16, secure_area_crc)?;
+    w!(secure_area_crc16, "Secure area CRC", "{:#06X} ({})\n", header.secure_area_crc16, secure_area_crc)?;
 
-    let delay_ms = header.secure_area_delay as f64 / 131.0;
-    w!("0x06E", "Secure area delay", "{:#06X} ({:.0} ms)\n", header.secure_area_delay, delay_ms)?;
+    w!(secure_area_delay, "Secure area delay", "{:#06X} ({:.0} ms)\n", header.secure_area_delay, header.secure_area_delay_ms())?;
 
-    w!("0x070", "ARM9 autoload hook RAM address?", "{:#X}\n", header.arm9_autoload)?;
-    w!("0x074", "ARM7 autoload hook RAM address?", "{:#X}\n", header.arm7_autoload)?;
+    w!(arm9_autoload, "ARM9 autoload hook RAM address?", "{:#X}\n", header.arm9_autoload)?;
+    w!(arm7_autoload, "ARM7 autoload hook RAM address?", "{:#X}\n", header.arm7_autoload)?;
 
-    w!("0x078", "Secure area disable", "{:#018X}\n", header.secure_area_disable)?;
+    w!(secure_area_disable, "Secure area disable", "{:#018X}\n", header.secure_area_disable)?;
 
-    w!("0x080", "ROM size", "{:#X}\n", header.rom_size)?;
-    w!("0x084", "ROM header size", "{:#X}\n", header.header_size)?;
+    w!(rom_size, "ROM size", "{:#X}\n", header.rom_size)?;
+    w!(header_size, "ROM header size", "{:#X}\n", header.header_size)?;
 
-    w!("0x088", "(4 bytes unknown)\n")?;
-    w!("0x08C", "(8 bytes reserved)\n")?;
+    w!(unknown1, "Unknown")?;
+    w!(reserved2, "Reserved")?;
 
-    w!("0x094", "NAND end of ROM area", "{:#06X}\n", header.nand_rom_end)?;
-    w!("0x096", "NAND start of RW area", "{:#06X}\n", header.nand_rw_start)?;
+    w!(nand_rom_end, "NAND end of ROM area", "{:#06X}\n", header.nand_rom_end)?;
+    w!(nand_rw_start, "NAND start of RW area", "{:#06X}\n", header.nand_rw_start)?;
 
-    w!("0x098", "(40 bytes reserved)\n")?;
+    w!(reserved3, "Reserved")?;
 
     let logo_crc = if header.compute_logo_crc16() == header.nintendo_logo_crc16 { "OK" } else { "INVALID" };
     let header_crc = if header.compute_header_crc16() == header.header_crc16 { "OK" } else { "INVALID" };
 
-    w!("0x0C0", "Nintendo logo (156 bytes)\n")?;
-    w!("0x15C", "Nintendo logo CRC", "{:#06X} ({})\n", header.nintendo_logo_crc16, logo_crc)?;
-    w!("0x15E", "Header CRC", "{:#06X} ({})\n", header.header_crc16, header_crc)?;
+    w!(nintendo_logo, "Nintendo logo")?;
+    w!(nintendo_logo_crc16, "Nintendo logo CRC", "{:#06X} ({})\n", header.nintendo_logo_crc16, logo_crc)?;
+    w!(header_crc16, "Header CRC", "{:#06X} ({})\n", header.header_crc16, header_crc)?;
 
-    w!("0x160", "Debug ROM offset", "{:#X}\n", header.debug_rom_offset)?;
-    w!("0x164", "Debug code size", "{:#X}\n", header.debug_size)?;
-    w!("0x168", "Debug RAM address", "{:#X}\n", header.debug_ram_address)?;
+    w!(debug_rom_offset, "Debug ROM offset", "{:#X}\n", header.debug_rom_offset)?;
+    w!(debug_size, "Debug code size", "{:#X}\n", header.debug_size)?;
+    w!(debug_ram_address, "Debug RAM address", "{:#X}\n", header.debug_ram_address)?;
 
-    w!("0x16C", "(4 bytes reserved)\n")?;
-    w!("0x170", "(144 bytes reserved)\n")?;
+    w!(reserved4, "Reserved")?;
+    w!(reserved5, "Reserved")?;
 
     Ok(())
 }
@@ -0,0 +1,217 @@
+use std::env;
+use std::error::Error;
+use std::io::{self, Write};
+use std::process;
+
+use rom::nds::{BannerVersion, CommandSettings, NdsBanner, NdsRom, RomParams};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct RomInfo {
+    header: HeaderInfo,
+    banner: Option<BannerInfo>,
+    checksums: Checksums,
+    params: RomParams,
+    chip_id: u32,
+}
+
+#[derive(Serialize)]
+struct HeaderInfo {
+    game_title: String,
+    game_code: String,
+    maker_code: String,
+    region: Option<&'static str>,
+    maker: Option<&'static str>,
+    unit_code: u8,
+    device_type: u8,
+    device_capacity: u8,
+    nds_region: u8,
+    rom_version: u8,
+    autostart: u8,
+    arm9_rom_offset: u32,
+    arm9_entry_address: u32,
+    arm9_ram_address: u32,
+    arm9_size: u32,
+    arm7_rom_offset: u32,
+    arm7_entry_address: u32,
+    arm7_ram_address: u32,
+    arm7_size: u32,
+    fnt_offset: u32,
+    fnt_size: u32,
+    fat_offset: u32,
+    fat_size: u32,
+    arm9_overlay_offset: u32,
+    arm9_overlay_size: u32,
+    arm7_overlay_offset: u32,
+    arm7_overlay_size: u32,
+    normal_command_settings: CommandSettings,
+    key1_command_settings: CommandSettings,
+    banner_offset: u32,
+    secure_area_delay_ms: f64,
+    arm9_autoload: u32,
+    arm7_autoload: u32,
+    secure_area_disable: u64,
+    rom_size: u32,
+    header_size: u32,
+    nand_rom_end: u16,
+    nand_rw_start: u16,
+    debug_rom_offset: u32,
+    debug_size: u32,
+    debug_ram_address: u32,
+}
+
+#[derive(Serialize)]
+struct BannerInfo {
+    version: BannerVersion,
+    title_japanese: String,
+    title_english: String,
+    title_french: String,
+    title_german: String,
+    title_italian: String,
+    title_spanish: String,
+    title_chinese: Option<String>,
+    title_korean: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Checksums {
+    logo: Checksum,
+    header: Checksum,
+    secure_area: Option<Checksum>,
+}
+
+#[derive(Serialize)]
+struct Checksum {
+    expected: u16,
+    computed: u16,
+    valid: bool,
+}
+
+impl Checksum {
+    fn new(expected: u16, computed: u16) -> Checksum {
+        Checksum {
+            expected,
+            computed,
+            valid: expected == computed,
+        }
+    }
+}
+
+impl RomInfo {
+    fn new(rom: &NdsRom) -> RomInfo {
+        let h = &rom.header;
+
+        let header = HeaderInfo {
+            game_title: h.game_title.to_string_lossy().into_owned(),
+            game_code: h.game_code.to_string_lossy().into_owned(),
+            maker_code: h.maker_code.to_string_lossy().into_owned(),
+            region: h.region(),
+            maker: h.maker(),
+            unit_code: h.unit_code,
+            device_type: h.device_type,
+            device_capacity: h.device_capacity,
+            nds_region: h.nds_region,
+            rom_version: h.rom_version,
+            autostart: h.autostart,
+            arm9_rom_offset: h.arm9_rom_offset,
+            arm9_entry_address: h.arm9_entry_address,
+            arm9_ram_address: h.arm9_ram_address,
+            arm9_size: h.arm9_size,
+            arm7_rom_offset: h.arm7_rom_offset,
+            arm7_entry_address: h.arm7_entry_address,
+            arm7_ram_address: h.arm7_ram_address,
+            arm7_size: h.arm7_size,
+            fnt_offset: h.fnt_offset,
+            fnt_size: h.fnt_size,
+            fat_offset: h.fat_offset,
+            fat_size: h.fat_size,
+            arm9_overlay_offset: h.arm9_overlay_offset,
+            arm9_overlay_size: h.arm9_overlay_size,
+            arm7_overlay_offset: h.arm7_overlay_offset,
+            arm7_overlay_size: h.arm7_overlay_size,
+            normal_command_settings: h.normal_command_settings(),
+            key1_command_settings: h.key1_command_settings(),
+            banner_offset: h.banner_offset,
+            secure_area_delay_ms: h.secure_area_delay_ms(),
+            arm9_autoload: h.arm9_autoload,
+            arm7_autoload: h.arm7_autoload,
+            secure_area_disable: h.secure_area_disable,
+            rom_size: h.rom_size,
+            header_size: h.header_size,
+            nand_rom_end: h.nand_rom_end,
+            nand_rw_start: h.nand_rw_start,
+            debug_rom_offset: h.debug_rom_offset,
+            debug_size: h.debug_size,
+            debug_ram_address: h.debug_ram_address,
+        };
+
+        let computed = rom.checksums();
+        let checksums = Checksums {
+            logo: Checksum::new(h.nintendo_logo_crc16, computed.logo),
+            header: Checksum::new(h.header_crc16, computed.header),
+            secure_area: computed
+                .secure_area
+                .map(|crc| Checksum::new(h.secure_area_crc16, crc)),
+        };
+
+        RomInfo {
+            header,
+            banner: rom.banner.as_ref().map(BannerInfo::new),
+            checksums,
+            params: rom.params,
+            chip_id: rom.chip_id,
+        }
+    }
+}
+
+impl BannerInfo {
+    fn new(banner: &NdsBanner) -> BannerInfo {
+        let version = banner.version();
+
+        BannerInfo {
+            version,
+            title_japanese: banner.title_japanese.to_string_lossy(),
+            title_english: banner.title_english.to_string_lossy(),
+            title_french: banner.title_french.to_string_lossy(),
+            title_german: banner.title_german.to_string_lossy(),
+            title_italian: banner.title_italian.to_string_lossy(),
+            title_spanish: banner.title_spanish.to_string_lossy(),
+            title_chinese: version
+                .has_chinese()
+                .then(|| banner.title_chinese.to_string_lossy()),
+            title_korean: version
+                .has_korean()
+                .then(|| banner.title_korean.to_string_lossy()),
+        }
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    pretty_env_logger::try_init_custom_env("RSDS_LOG").unwrap();
+
+    let file = match env::args_os().nth(1) {
+        Some(file) => file,
+        None => {
+            println!("usage: ndsjson <rom>");
+            process::exit(0);
+        }
+    };
+
+    let rom = match NdsRom::open(file) {
+        Ok(rom) => rom,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    };
+
+    let stdout = io::stdout();
+    let mut f = stdout.lock();
+
+    serde_json::to_writer_pretty(&mut f, &RomInfo::new(&rom))?;
+    writeln!(f)?;
+
+    f.flush()?;
+
+    Ok(())
+}
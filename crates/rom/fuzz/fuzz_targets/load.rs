@@ -0,0 +1,35 @@
+//! Feeds arbitrary bytes to `NdsRom::load`, checking that loading and the
+//! accessors on a loaded ROM never panic.
+//!
+//! Run with `cargo fuzz run load` from `crates/rom`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rom::nds::NdsRom;
+
+fn exercise(rom: &NdsRom) {
+    let _ = rom.display_name();
+    let _ = rom.verify();
+    let _ = rom.layout_report();
+    let _ = rom.checksums();
+    let _ = rom.secure_area_id();
+    let _ = rom.arm9_build_info();
+    let _ = rom.overlay_file_map();
+    for overlay in rom.arm9_overlays().chain(rom.arm7_overlays()).take(64) {
+        let _ = rom.overlay_footer(&overlay);
+        let _ = rom.decompress_overlay(&overlay);
+    }
+    if let Ok(fs) = rom.filesystem() {
+        let _ = fs.find("a/b/c");
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(rom) = NdsRom::load(data) {
+        exercise(&rom);
+    }
+    if let (Some(rom), _) = NdsRom::try_load(data) {
+        exercise(&rom);
+    }
+});
@@ -0,0 +1,52 @@
+use std::fmt;
+
+/// An offset into the ROM data, as in the `*_rom_offset` header fields.
+///
+/// Distinct from [`RamAddress`], so that the two cannot be mixed up.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct RomOffset(pub u32);
+
+/// An address in the memory map, as in the `*_ram_address` and
+/// `*_entry_address` header fields.
+///
+/// Distinct from [`RomOffset`], so that the two cannot be mixed up.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct RamAddress(pub u32);
+
+impl RomOffset {
+    /// Returns the offset as an index into the ROM data.
+    #[inline]
+    pub fn as_usize(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl fmt::Display for RomOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#010X}", self.0)
+    }
+}
+
+impl fmt::Display for RamAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#010X}", self.0)
+    }
+}
+
+impl From<RomOffset> for u32 {
+    #[inline]
+    fn from(offset: RomOffset) -> u32 {
+        offset.0
+    }
+}
+
+impl From<RamAddress> for u32 {
+    #[inline]
+    fn from(address: RamAddress) -> u32 {
+        address.0
+    }
+}
@@ -0,0 +1,139 @@
+//! Extraction of ROMs from `.zip` and `.7z` archives.
+
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::nds::error::ArchiveError;
+use crate::nds::format::RomFormat;
+use crate::nds::MAX_ROM_SIZE;
+
+const ZIP_MAGIC: [u8; 4] = *b"PK\x03\x04";
+const SEVEN_Z_MAGIC: [u8; 6] = *b"7z\xBC\xAF\x27\x1C";
+
+/// A supported archive format.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ArchiveKind {
+    Zip,
+    SevenZ,
+}
+
+impl ArchiveKind {
+    /// Detects the archive format by file extension, or by magic bytes.
+    ///
+    /// The reader is rewound to the start.
+    pub(crate) fn detect<R: Read + Seek>(
+        path: &Path,
+        reader: &mut R,
+    ) -> io::Result<Option<ArchiveKind>> {
+        let ext = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match ext.as_deref() {
+            Some("zip") => return Ok(Some(ArchiveKind::Zip)),
+            Some("7z") => return Ok(Some(ArchiveKind::SevenZ)),
+            _ => {}
+        }
+
+        let mut magic = [0u8; 6];
+        let mut len = 0;
+        while len < magic.len() {
+            match reader.read(&mut magic[len..]) {
+                Ok(0) => break,
+                Ok(n) => len += n,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        reader.seek(SeekFrom::Start(0))?;
+
+        if magic[..len].starts_with(&ZIP_MAGIC) {
+            Ok(Some(ArchiveKind::Zip))
+        } else if magic[..len].starts_with(&SEVEN_Z_MAGIC) {
+            Ok(Some(ArchiveKind::SevenZ))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Extracts the single ROM contained in the archive.
+    pub(crate) fn extract<R: Read + Seek>(
+        self,
+        reader: R,
+    ) -> Result<Cursor<Vec<u8>>, ArchiveError> {
+        match self {
+            ArchiveKind::Zip => extract_zip(reader),
+            ArchiveKind::SevenZ => extract_7z(reader),
+        }
+    }
+}
+
+fn is_rom_name(name: &str) -> bool {
+    match name.rsplit_once('.') {
+        Some((_, ext)) => RomFormat::from_extension(ext).is_some(),
+        None => false,
+    }
+}
+
+fn extract_zip<R: Read + Seek>(reader: R) -> Result<Cursor<Vec<u8>>, ArchiveError> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+
+    let mut found = None;
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i)?;
+        if entry.is_file() && is_rom_name(entry.name()) {
+            if found.is_some() {
+                return Err(ArchiveError::MultipleRoms);
+            }
+            found = Some(i);
+        }
+    }
+
+    let mut entry = archive.by_index(found.ok_or(ArchiveError::NoRom)?)?;
+    // Capped, so that a corrupt archive cannot abort on allocation.
+    let mut buf = Vec::with_capacity(entry.size().min(MAX_ROM_SIZE as u64) as usize);
+    entry
+        .read_to_end(&mut buf)
+        .map_err(zip::result::ZipError::Io)?;
+
+    Ok(Cursor::new(buf))
+}
+
+fn extract_7z<R: Read + Seek>(mut reader: R) -> Result<Cursor<Vec<u8>>, ArchiveError> {
+    let len = reader
+        .seek(SeekFrom::End(0))
+        .map_err(sevenz_rust::Error::io)?;
+    reader
+        .seek(SeekFrom::Start(0))
+        .map_err(sevenz_rust::Error::io)?;
+
+    let mut archive = sevenz_rust::SevenZReader::new(reader, len, sevenz_rust::Password::empty())?;
+
+    let count = archive
+        .archive()
+        .files
+        .iter()
+        .filter(|entry| !entry.is_directory() && is_rom_name(entry.name()))
+        .count();
+    match count {
+        0 => return Err(ArchiveError::NoRom),
+        1 => {}
+        _ => return Err(ArchiveError::MultipleRoms),
+    }
+
+    let mut buf = Vec::new();
+    archive.for_each_entries(|entry, entry_reader| {
+        if !entry.is_directory() && is_rom_name(entry.name()) {
+            buf.reserve(entry.size().min(MAX_ROM_SIZE as u64) as usize);
+            entry_reader.read_to_end(&mut buf)?;
+            // Stop iterating, the ROM has been found.
+            Ok(false)
+        } else {
+            // Entries must be read to advance within solid blocks.
+            io::copy(entry_reader, &mut io::sink())?;
+            Ok(true)
+        }
+    })?;
+
+    Ok(Cursor::new(buf))
+}
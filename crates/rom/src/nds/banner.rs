@@ -1,6 +1,9 @@
 use std::mem;
 
 use common::str::Utf16;
+use common::util::crc;
+
+use crate::nds::BannerError;
 
 /// NDS ROM icon/title.
 ///
@@ -35,7 +38,7 @@ pub struct NdsBanner {
     /// - Entry 0 at `0x0002` = CRC16 for `0x0020..=0x083F` (all versions)
     /// - Entry 1 at `0x0004` = CRC16 for `0x0020..=0x093F` (version `0x0002` and above)
     /// - Entry 2 at `0x0006` = CRC16 for `0x0020..=0x0A3F` (version `0x0003` and above)
-    /// - Entry 3 at `0x0006` = CRC16 for `0x1240..=0x23BF` (version `0x0103` and above)
+    /// - Entry 3 at `0x0008` = CRC16 for `0x1240..=0x23BF` (version `0x0103` and above)
     pub crc16: [u16; 4], // 0x0002
     /// Reserved, zero filled.
     reserved1: [u8; 22], // 0x000A
@@ -96,6 +99,132 @@ pub struct NdsBanner {
 }
 
 static_assert!(NdsBanner::SIZE == 0x23C0);
+static_assert!(mem::offset_of!(NdsBanner, crc16) == 0x0002);
+static_assert!(mem::offset_of!(NdsBanner, icon) == 0x0020);
+static_assert!(mem::offset_of!(NdsBanner, palette) == 0x0220);
+static_assert!(mem::offset_of!(NdsBanner, title_japanese) == 0x0240);
+static_assert!(mem::offset_of!(NdsBanner, title_korean) == 0x0940);
+static_assert!(mem::offset_of!(NdsBanner, dsi_icon) == 0x1240);
+static_assert!(mem::offset_of!(NdsBanner, dsi_palette) == 0x2240);
+static_assert!(mem::offset_of!(NdsBanner, dsi_sequence) == 0x2340);
+
+/// NDS ROM icon/title version.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BannerVersion {
+    /// `0x0001`, original.
+    Original,
+    /// `0x0002`, with Chinese title.
+    Chinese,
+    /// `0x0003`, with Chinese and Korean titles.
+    Korean,
+    /// `0x0103`, with Chinese and Korean titles, and animated DSi icon.
+    Dsi,
+    /// Unknown version.
+    Unknown(u16),
+}
+
+/// A language of the banner titles.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum BannerLanguage {
+    /// Japanese.
+    Japanese,
+    /// English.
+    English,
+    /// French.
+    French,
+    /// German.
+    German,
+    /// Italian.
+    Italian,
+    /// Spanish.
+    Spanish,
+    /// Chinese, from [`BannerVersion::Chinese`].
+    Chinese,
+    /// Korean, from [`BannerVersion::Korean`].
+    Korean,
+}
+
+impl BannerLanguage {
+    /// All languages, in the order of the titles in the banner.
+    pub const ALL: [BannerLanguage; 8] = [
+        BannerLanguage::Japanese,
+        BannerLanguage::English,
+        BannerLanguage::French,
+        BannerLanguage::German,
+        BannerLanguage::Italian,
+        BannerLanguage::Spanish,
+        BannerLanguage::Chinese,
+        BannerLanguage::Korean,
+    ];
+}
+
+impl BannerVersion {
+    /// Returns `true` if the banner has a title in the given language.
+    pub fn has_language(self, lang: BannerLanguage) -> bool {
+        match lang {
+            BannerLanguage::Chinese => self.has_chinese(),
+            BannerLanguage::Korean => self.has_korean(),
+            _ => true,
+        }
+    }
+
+    /// Returns `true` if the banner has a Chinese title.
+    pub fn has_chinese(self) -> bool {
+        matches!(
+            self,
+            BannerVersion::Chinese | BannerVersion::Korean | BannerVersion::Dsi
+        )
+    }
+
+    /// Returns `true` if the banner has a Korean title.
+    pub fn has_korean(self) -> bool {
+        matches!(self, BannerVersion::Korean | BannerVersion::Dsi)
+    }
+
+    /// Returns `true` if the banner has an animated DSi icon.
+    pub fn has_dsi_animation(self) -> bool {
+        matches!(self, BannerVersion::Dsi)
+    }
+
+    /// Returns the size of the banner data in bytes.
+    ///
+    /// Unknown versions are assumed to use the full [`NdsBanner::SIZE`].
+    pub fn size(self) -> usize {
+        match self {
+            BannerVersion::Original => 0x0840,
+            BannerVersion::Chinese => 0x0940,
+            BannerVersion::Korean => 0x0A40,
+            BannerVersion::Dsi | BannerVersion::Unknown(_) => NdsBanner::SIZE,
+        }
+    }
+}
+
+impl From<u16> for BannerVersion {
+    fn from(version: u16) -> Self {
+        match version {
+            0x0001 => BannerVersion::Original,
+            0x0002 => BannerVersion::Chinese,
+            0x0003 => BannerVersion::Korean,
+            0x0103 => BannerVersion::Dsi,
+            version => BannerVersion::Unknown(version),
+        }
+    }
+}
+
+impl From<BannerVersion> for u16 {
+    fn from(version: BannerVersion) -> Self {
+        match version {
+            BannerVersion::Original => 0x0001,
+            BannerVersion::Chinese => 0x0002,
+            BannerVersion::Korean => 0x0003,
+            BannerVersion::Dsi => 0x0103,
+            BannerVersion::Unknown(version) => version,
+        }
+    }
+}
 
 impl NdsBanner {
     /// The size of a banner in bytes.
@@ -116,7 +245,347 @@ impl NdsBanner {
 
         let bytes = &rom[offset..(offset + NdsBanner::SIZE)];
 
+        // The `Utf16` titles keep the little-endian code units of the ROM, and
+        // convert to host endianness when decoded, so need no byte swapping.
+        //
         // SAFETY: `bytes` is valid for reads of `NdsBanner::SIZE` bytes.
         unsafe { read(bytes) }
     }
+
+    /// Parses a standalone banner, such as an extracted banner file.
+    ///
+    /// The data must cover the size of its banner version, see
+    /// [`BannerVersion::size`], and is zero padded to [`NdsBanner::SIZE`].
+    /// Trailing data is ignored. The checksums are not verified, see
+    /// [`compute_crc16`](NdsBanner::compute_crc16).
+    pub fn parse(bytes: &[u8]) -> Result<NdsBanner, BannerError> {
+        let &[lo, hi, ..] = bytes else {
+            return Err(BannerError::TooSmall {
+                size: bytes.len(),
+                expected: 2,
+            });
+        };
+
+        let version = BannerVersion::from(u16::from_le_bytes([lo, hi]));
+        if let BannerVersion::Unknown(version) = version {
+            return Err(BannerError::UnknownVersion(version));
+        }
+        if bytes.len() < version.size() {
+            return Err(BannerError::TooSmall {
+                size: bytes.len(),
+                expected: version.size(),
+            });
+        }
+
+        let mut padded = [0; NdsBanner::SIZE];
+        let len = bytes.len().min(NdsBanner::SIZE);
+        padded[..len].copy_from_slice(&bytes[..len]);
+
+        Ok(NdsBanner::read(&padded, 0))
+    }
+
+    /// Returns the banner version.
+    pub fn version(&self) -> BannerVersion {
+        BannerVersion::from(self.version)
+    }
+
+    /// Returns the title in the given language.
+    ///
+    /// The title is returned regardless of whether the banner version has the
+    /// language, see [`BannerVersion::has_language`].
+    pub fn title(&self, lang: BannerLanguage) -> &Utf16<128> {
+        match lang {
+            BannerLanguage::Japanese => &self.title_japanese,
+            BannerLanguage::English => &self.title_english,
+            BannerLanguage::French => &self.title_french,
+            BannerLanguage::German => &self.title_german,
+            BannerLanguage::Italian => &self.title_italian,
+            BannerLanguage::Spanish => &self.title_spanish,
+            BannerLanguage::Chinese => &self.title_chinese,
+            BannerLanguage::Korean => &self.title_korean,
+        }
+    }
+
+    /// Returns the best title to display, in the `preferred` language if the
+    /// banner has a title in it, otherwise falling back to English, Japanese,
+    /// then the first title present.
+    ///
+    /// Languages not covered by the banner version are skipped, as are empty
+    /// titles and titles filled with `0xFFFF`. Invalid characters are replaced
+    /// by `U+FFFD`, and surrounding whitespace is trimmed.
+    pub fn best_title(&self, preferred: BannerLanguage) -> Option<String> {
+        let version = self.version();
+        let fallback = [BannerLanguage::English, BannerLanguage::Japanese];
+
+        [preferred]
+            .into_iter()
+            .chain(fallback)
+            .chain(BannerLanguage::ALL)
+            .filter(|&lang| version.has_language(lang))
+            .map(|lang| self.title(lang))
+            .filter(|title| title.units().iter().any(|&c| c != 0xFFFF))
+            .map(|title| title.to_string_lossy().trim().to_owned())
+            .find(|title| !title.is_empty())
+    }
+
+    /// Returns a mutable reference to the title in the given language.
+    ///
+    /// The checksums are not updated, see [`fix_crc`](NdsBanner::fix_crc).
+    pub fn title_mut(&mut self, lang: BannerLanguage) -> &mut Utf16<128> {
+        match lang {
+            BannerLanguage::Japanese => &mut self.title_japanese,
+            BannerLanguage::English => &mut self.title_english,
+            BannerLanguage::French => &mut self.title_french,
+            BannerLanguage::German => &mut self.title_german,
+            BannerLanguage::Italian => &mut self.title_italian,
+            BannerLanguage::Spanish => &mut self.title_spanish,
+            BannerLanguage::Chinese => &mut self.title_chinese,
+            BannerLanguage::Korean => &mut self.title_korean,
+        }
+    }
+
+    /// Recomputes the checksums covered by the banner version, and writes them
+    /// to [`crc16`].
+    ///
+    /// Entries not covered by the banner version are left unchanged, see
+    /// [`compute_crc16`](NdsBanner::compute_crc16). Call this after editing
+    /// the titles or icon, to produce a valid banner.
+    ///
+    /// [`crc16`]: #structfield.crc16
+    pub fn fix_crc(&mut self) {
+        let crcs = self.compute_crc16();
+        for (stored, crc) in self.crc16.iter_mut().zip(crcs) {
+            if let Some(crc) = crc {
+                *stored = crc;
+            }
+        }
+    }
+
+    /// Returns the icon palette as RGBA colours.
+    ///
+    /// Colour 0 is transparent.
+    pub fn palette_rgba(&self) -> [[u8; 4]; 16] {
+        palette_rgba(&self.palette)
+    }
+
+    /// Computes the banner checksums.
+    ///
+    /// Entries not covered by the banner version are `None`, see [`crc16`].
+    ///
+    /// [`crc16`]: #structfield.crc16
+    pub fn compute_crc16(&self) -> [Option<u16>; 4] {
+        let bytes = self.as_bytes();
+
+        let version = self.version();
+        [
+            Some(crc::crc16(&bytes[0x0020..0x0840])),
+            version
+                .has_chinese()
+                .then(|| crc::crc16(&bytes[0x0020..0x0940])),
+            version
+                .has_korean()
+                .then(|| crc::crc16(&bytes[0x0020..0x0A40])),
+            version
+                .has_dsi_animation()
+                .then(|| crc::crc16(&bytes[0x1240..0x23C0])),
+        ]
+    }
+
+    /// Computes the CRC-32 of the icon bitmap and palette.
+    ///
+    /// Can be used as a key for caching the decoded icon, or to detect ROMs
+    /// which share an icon.
+    pub fn icon_crc32(&self) -> u32 {
+        crc::crc32(&self.as_bytes()[0x0020..0x0240])
+    }
+
+    /// Computes the CRC-32 of the DSi animated icon bitmaps, palettes and
+    /// sequence.
+    ///
+    /// Returns `None` if the banner version has no DSi animated icon.
+    pub fn dsi_icon_crc32(&self) -> Option<u32> {
+        self.version()
+            .has_dsi_animation()
+            .then(|| crc::crc32(&self.as_bytes()[0x1240..0x23C0]))
+    }
+
+    /// Returns the banner as bytes, as stored in ROM.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        let ptr = self as *const NdsBanner as *const u8;
+        // SAFETY: `NdsBanner` is `repr(C)` with no padding.
+        unsafe { std::slice::from_raw_parts(ptr, NdsBanner::SIZE) }
+    }
+
+    /// Decodes the icon bitmap into palette indices, indexed by `[y][x]`.
+    pub fn icon_bitmap(&self) -> [[u8; 32]; 32] {
+        decode_bitmap(&self.icon)
+    }
+
+    /// Writes the icon as a PNG image.
+    ///
+    /// Palette index 0 is written as transparent.
+    #[cfg(feature = "image")]
+    pub fn write_icon_png<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        let mut writer = png_encoder(w).write_header()?;
+        writer.write_image_data(&rgba(&self.icon_bitmap(), &self.palette))?;
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Writes the animated DSi icon as an APNG image.
+    ///
+    /// If the banner has no animated icon, the static icon is written instead.
+    #[cfg(feature = "image")]
+    pub fn write_dsi_icon_apng<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        let sequence: Vec<u16> = if self.version().has_dsi_animation() {
+            self.dsi_sequence
+                .iter()
+                .copied()
+                .take_while(|&token| token != 0)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        if sequence.is_empty() {
+            return self.write_icon_png(w);
+        }
+
+        let mut encoder = png_encoder(w);
+        encoder.set_animated(sequence.len() as u32, 0)?;
+
+        let mut writer = encoder.write_header()?;
+        for token in sequence {
+            // Sequence token format:
+            //   15    flip vertically
+            //   14    flip horizontally
+            //   13-11 palette index
+            //   10-8  bitmap index
+            //   7-0   frame duration (in 60Hz units)
+            let flip_v = token & 0x8000 != 0;
+            let flip_h = token & 0x4000 != 0;
+            let palette = &self.dsi_palette[((token >> 11) & 0x7) as usize];
+            let mut bitmap = decode_bitmap(&self.dsi_icon[((token >> 8) & 0x7) as usize]);
+            let duration = token & 0xFF;
+
+            if flip_v {
+                bitmap.reverse();
+            }
+            if flip_h {
+                bitmap.iter_mut().for_each(|row| row.reverse());
+            }
+
+            writer.set_frame_delay(duration, 60)?;
+            writer.write_image_data(&rgba(&bitmap, palette))?;
+        }
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Renders the icon for a truecolor terminal.
+    ///
+    /// Each character cell contains two vertical pixels, drawn with half-block
+    /// characters. Transparent pixels are left as the terminal background.
+    #[cfg(feature = "ansi")]
+    pub fn icon_ansi(&self) -> String {
+        use std::fmt::Write;
+
+        let bitmap = self.icon_bitmap();
+        let color = |index: u8| rgb(self.palette[index as usize]);
+
+        let mut s = String::new();
+        for rows in bitmap.chunks_exact(2) {
+            for (&top, &bottom) in rows[0].iter().zip(&rows[1]) {
+                // Palette index 0 is transparent.
+                let _ = match (top, bottom) {
+                    (0, 0) => write!(s, "\x1b[0m "),
+                    (top, 0) => {
+                        let (r, g, b) = color(top);
+                        write!(s, "\x1b[0;38;2;{};{};{}m\u{2580}", r, g, b)
+                    }
+                    (0, bottom) => {
+                        let (r, g, b) = color(bottom);
+                        write!(s, "\x1b[0;38;2;{};{};{}m\u{2584}", r, g, b)
+                    }
+                    (top, bottom) => {
+                        let (r, g, b) = color(top);
+                        let (br, bg, bb) = color(bottom);
+                        write!(
+                            s,
+                            "\x1b[0;38;2;{};{};{};48;2;{};{};{}m\u{2580}",
+                            r, g, b, br, bg, bb
+                        )
+                    }
+                };
+            }
+            s.push_str("\x1b[0m\n");
+        }
+
+        s
+    }
+}
+
+/// Decodes an icon bitmap into palette indices, indexed by `[y][x]`.
+fn decode_bitmap(icon: &[u8; 512]) -> [[u8; 32]; 32] {
+    let mut bitmap = [[0u8; 32]; 32];
+
+    // The icon is made up of 4x4 tiles, each 8x8 pixels, with 2 pixels per
+    // byte (lower nibble first).
+    for (i, &byte) in icon.iter().enumerate() {
+        let tile = i / 32;
+        let x = (tile % 4) * 8 + (i % 4) * 2;
+        let y = (tile / 4) * 8 + (i % 32) / 4;
+
+        bitmap[y][x] = byte & 0x0F;
+        bitmap[y][x + 1] = byte >> 4;
+    }
+
+    bitmap
+}
+
+/// Converts a bitmap into RGBA pixels, in row-major order.
+///
+/// Palette index 0 is transparent.
+#[cfg(feature = "image")]
+fn rgba(bitmap: &[[u8; 32]; 32], palette: &[u16; 16]) -> Vec<u8> {
+    let palette = palette_rgba(palette);
+
+    bitmap
+        .iter()
+        .flatten()
+        .flat_map(|&index| palette[index as usize])
+        .collect()
+}
+
+#[cfg(feature = "image")]
+fn png_encoder<W: std::io::Write>(w: W) -> png::Encoder<'static, W> {
+    let mut encoder = png::Encoder::new(w, 32, 32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+}
+
+/// Converts a BGR555 palette to RGBA colours.
+///
+/// Colour 0 is transparent.
+fn palette_rgba(palette: &[u16; 16]) -> [[u8; 4]; 16] {
+    let mut colors = [[0u8; 4]; 16];
+    for (color, &bgr) in colors.iter_mut().zip(palette).skip(1) {
+        let (r, g, b) = rgb(bgr);
+        *color = [r, g, b, 0xFF];
+    }
+    colors
+}
+
+/// Converts a BGR555 colour to 8-bit RGB components.
+fn rgb(color: u16) -> (u8, u8, u8) {
+    #[inline(always)]
+    fn scale(c: u16) -> u8 {
+        let c = (c & 0x1F) as u8;
+        (c << 3) | (c >> 2)
+    }
+
+    (scale(color), scale(color >> 5), scale(color >> 10))
 }
@@ -0,0 +1,112 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+/// Footer of data compressed with the BIOS-compatible backwards LZ77 (BLZ),
+/// used by the ARM9 binary and overlays.
+///
+/// Stored in the last 8 bytes of the compressed data:
+///
+/// - `0x00` = bit0-23: compressed length, bit24-31: header length
+/// - `0x04` = decompressed length increase
+///
+/// # Sources
+///
+/// \[1\]: <https://problemkaputt.de/gbatek.htm#biosdecompressionfunctions>
+///
+/// \[2\]: <https://github.com/devkitPro/ndstool/blob/master/source/overlay.h>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BlzFooter {
+    /// The length of the compressed region, counted back from the end of the
+    /// data, including the header.
+    ///
+    /// Data before the compressed region is stored uncompressed.
+    pub compressed_len: u32,
+    /// The length of the header, counted back from the end of the data,
+    /// including the footer and any padding.
+    pub header_len: u8,
+    /// The number of bytes the data grows by when decompressed.
+    pub extra_len: u32,
+}
+
+impl BlzFooter {
+    /// The size of the footer in bytes.
+    pub const SIZE: usize = 8;
+
+    /// Reads the footer from the end of compressed `data`.
+    pub fn read(data: &[u8]) -> Option<BlzFooter> {
+        let footer = data.get(data.len().checked_sub(Self::SIZE)?..)?;
+        let word = LittleEndian::read_u32(&footer[0..4]);
+
+        Some(BlzFooter {
+            compressed_len: word & 0x00FFFFFF,
+            header_len: (word >> 24) as u8,
+            extra_len: LittleEndian::read_u32(&footer[4..8]),
+        })
+    }
+
+    /// Returns the length of the decompressed data, given the length of the
+    /// compressed data.
+    #[inline]
+    pub fn decompressed_len(&self, len: usize) -> usize {
+        len + self.extra_len as usize
+    }
+}
+
+/// Decompresses data compressed with the backwards LZ77 (BLZ), see
+/// [`BlzFooter`].
+///
+/// Returns `None` if the data is corrupt.
+pub fn decompress_blz(data: &[u8]) -> Option<Vec<u8>> {
+    let footer = BlzFooter::read(data)?;
+    let len = data.len();
+
+    let header_len = footer.header_len as usize;
+    let compressed_len = footer.compressed_len as usize;
+    if header_len < BlzFooter::SIZE || header_len > compressed_len || compressed_len > len {
+        return None;
+    }
+    // A flag byte and 8 pairs (17 bytes) expand to at most 144 bytes, reject
+    // footers claiming more before allocating the output.
+    if compressed_len + footer.extra_len as usize > (compressed_len - header_len) * 9 {
+        return None;
+    }
+
+    let mut out = vec![0; footer.decompressed_len(len)];
+    let start = len - compressed_len;
+    out[..start].copy_from_slice(&data[..start]);
+
+    // Both the compressed data and decompressed data are processed backwards,
+    // from the end.
+    let mut src = len - header_len;
+    let mut dst = out.len();
+    while src > start {
+        src -= 1;
+        let flags = data[src];
+
+        for bit in (0..8).rev() {
+            if src <= start {
+                break;
+            }
+
+            if flags & (1 << bit) == 0 {
+                src -= 1;
+                dst = dst.checked_sub(1)?;
+                out[dst] = data[src];
+            } else {
+                if src < start + 2 {
+                    return None;
+                }
+                src -= 2;
+                let pair = u16::from_be_bytes([data[src + 1], data[src]]) as usize;
+                let count = (pair >> 12) + 3;
+                let disp = (pair & 0xFFF) + 3;
+
+                for _ in 0..count {
+                    dst = dst.checked_sub(1)?;
+                    out[dst] = *out.get(dst + disp)?;
+                }
+            }
+        }
+    }
+
+    (dst == start).then_some(out)
+}
@@ -0,0 +1,76 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+/// Magic value of the Nitro SDK footer and build info.
+const NITROCODE: u32 = 0xDEC00621;
+
+/// ARM9 build info, also known as the module params.
+///
+/// Located by the footer which follows the ARM9 binary in ROM:
+///
+/// - `0x00` = magic `0xDEC00621`
+/// - `0x04` = offset of the build info (relative to the ARM9 binary)
+/// - `0x08` = unknown, usually zero
+///
+/// Addresses are ARM9 RAM addresses.
+///
+/// # Sources
+///
+/// \[1\]: <https://github.com/devkitPro/ndstool/blob/master/source/header.h>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Arm9BuildInfo {
+    /// Start address of the autoload list.
+    pub autoload_list_start: u32,
+    /// End address of the autoload list.
+    pub autoload_list_end: u32,
+    /// Start address of the autoload data.
+    pub autoload_start: u32,
+    /// Start address of the static BSS section.
+    pub bss_start: u32,
+    /// End address of the static BSS section.
+    pub bss_end: u32,
+    /// End address of the compressed static module.
+    ///
+    /// `0x0000` if the ARM9 binary is not compressed.
+    pub compressed_static_end: u32,
+    /// Nitro SDK version.
+    pub sdk_version: u32,
+}
+
+impl Arm9BuildInfo {
+    /// The size of the build info in bytes.
+    pub const SIZE: usize = 0x24;
+
+    /// The size of the footer in bytes.
+    pub const FOOTER_SIZE: usize = 12;
+
+    /// Reads the build info of an ARM9 binary, given the ARM9 footer.
+    pub(crate) fn read(arm9: &[u8], footer: &[u8]) -> Option<Arm9BuildInfo> {
+        if footer.len() < Self::FOOTER_SIZE || LittleEndian::read_u32(&footer[0..4]) != NITROCODE {
+            return None;
+        }
+
+        let offset = LittleEndian::read_u32(&footer[4..8]) as usize;
+        let info = arm9.get(offset..offset.checked_add(Self::SIZE)?)?;
+
+        let word = |i: usize| LittleEndian::read_u32(&info[(i * 4)..(i * 4 + 4)]);
+        if word(7) != NITROCODE || word(8) != NITROCODE.swap_bytes() {
+            return None;
+        }
+
+        Some(Arm9BuildInfo {
+            autoload_list_start: word(0),
+            autoload_list_end: word(1),
+            autoload_start: word(2),
+            bss_start: word(3),
+            bss_end: word(4),
+            compressed_static_end: word(5),
+            sdk_version: word(6),
+        })
+    }
+
+    /// Returns `true` if the ARM9 binary is compressed.
+    #[inline]
+    pub fn is_compressed(&self) -> bool {
+        self.compressed_static_end != 0
+    }
+}
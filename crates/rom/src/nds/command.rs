@@ -0,0 +1,147 @@
+use std::fmt;
+
+/// Cartridge transfer settings, as written to port `0x40001A4` (ROMCTRL).
+///
+/// Only the bits stored in the header are decoded, the remaining bits of the
+/// port are status/control bits which are set at runtime.
+///
+/// # Sources
+///
+/// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgeioports>
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommandSettings {
+    /// KEY1 gap1 length (leading gap).
+    ///
+    /// `0x0000..=0x1FFF`, forced minimum `0x08F8` by BIOS.
+    pub gap1_length: u16,
+    /// KEY2 encryption for data.
+    pub key2_data: bool,
+    /// Unknown "SE" bit, usually the same as [`key2_data`].
+    ///
+    /// [`key2_data`]: #structfield.key2_data
+    pub se: bool,
+    /// KEY1 gap2 length (`0x200` byte gap).
+    ///
+    /// `0x00..=0x3F`, forced minimum `0x18` by BIOS.
+    pub gap2_length: u8,
+    /// KEY2 encryption for commands.
+    pub key2_command: bool,
+    /// Transfer clock rate.
+    pub clock_rate: ClockRate,
+    /// Output dummy clock pulses during gaps, instead of holding the clock high.
+    pub gap_clocks: bool,
+}
+
+/// Cartridge transfer clock rate.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClockRate {
+    /// 6.7MHz (33.51MHz / 5).
+    Fast,
+    /// 4.2MHz (33.51MHz / 8).
+    Slow,
+}
+
+/// A phase of the cartridge protocol, by the encryption of the commands.
+///
+/// The chip ID response is the same in every phase, only the encryption of
+/// the command differs, see [`NdsRom::chip_id_for_phase`].
+///
+/// [`NdsRom::chip_id_for_phase`]: crate::nds::NdsRom::chip_id_for_phase
+///
+/// # Sources
+///
+/// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgeprotocol>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CartPhase {
+    /// Unencrypted commands, after reset.
+    Unencrypted,
+    /// KEY1 encrypted commands, while loading the secure area.
+    Key1,
+    /// KEY2 encrypted commands, for the main data transfer.
+    Key2,
+}
+
+impl CommandSettings {
+    /// Decodes the settings from the raw port value.
+    pub fn from_bits(bits: u32) -> CommandSettings {
+        CommandSettings {
+            gap1_length: (bits & 0x1FFF) as u16,
+            key2_data: bits & (1 << 13) != 0,
+            se: bits & (1 << 14) != 0,
+            gap2_length: ((bits >> 16) & 0x3F) as u8,
+            key2_command: bits & (1 << 22) != 0,
+            clock_rate: if bits & (1 << 27) != 0 {
+                ClockRate::Slow
+            } else {
+                ClockRate::Fast
+            },
+            gap_clocks: bits & (1 << 28) != 0,
+        }
+    }
+
+    /// Encodes the settings into the raw port value.
+    pub fn to_bits(self) -> u32 {
+        let mut bits =
+            (self.gap1_length as u32 & 0x1FFF) | ((self.gap2_length as u32 & 0x3F) << 16);
+        if self.key2_data {
+            bits |= 1 << 13;
+        }
+        if self.se {
+            bits |= 1 << 14;
+        }
+        if self.key2_command {
+            bits |= 1 << 22;
+        }
+        if self.clock_rate == ClockRate::Slow {
+            bits |= 1 << 27;
+        }
+        if self.gap_clocks {
+            bits |= 1 << 28;
+        }
+        bits
+    }
+}
+
+impl ClockRate {
+    /// The cartridge bus base clock, in Hz.
+    pub const BASE_CLOCK_HZ: u32 = 33_513_982;
+
+    /// Returns the number of base clock cycles per transfer clock.
+    pub fn divider(self) -> u32 {
+        match self {
+            ClockRate::Fast => 5,
+            ClockRate::Slow => 8,
+        }
+    }
+
+    /// Returns the transfer clock frequency, in Hz.
+    pub fn hz(self) -> f64 {
+        ClockRate::BASE_CLOCK_HZ as f64 / self.divider() as f64
+    }
+}
+
+impl fmt::Display for CommandSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, gap1 {:#06X}, gap2 {:#04X}",
+            self.clock_rate, self.gap1_length, self.gap2_length
+        )?;
+        if self.key2_command || self.key2_data {
+            f.write_str(", KEY2")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ClockRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ClockRate::Fast => "6.7MHz",
+            ClockRate::Slow => "4.2MHz",
+        })
+    }
+}
@@ -0,0 +1,152 @@
+use common::util::crc::Crc32;
+use common::util::md5::Md5;
+use common::util::sha1::Sha1;
+
+use crate::nds::encrypt::{Key1, SecureAreaId, DESTROYED_ID};
+use crate::nds::NdsRom;
+
+/// The expected size and hashes of a ROM, as listed in a DAT file such as
+/// those of No-Intro.
+///
+/// Used by [`NdsRom::verify_against`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DatEntry {
+    /// The size of the ROM file in bytes.
+    pub size: u64,
+    /// The CRC-32 of the ROM file, see [`Hashes::crc32`].
+    ///
+    /// [`Hashes::crc32`]: crate::nds::Hashes::crc32
+    pub crc32: u32,
+    /// The MD5 digest of the ROM file, if listed.
+    pub md5: Option<[u8; 16]>,
+    /// The SHA-1 digest of the ROM file, if listed.
+    pub sha1: Option<[u8; 20]>,
+}
+
+/// The form of the secure area in the ROM images listed by a DAT file.
+///
+/// No-Intro publishes DAT files for both forms.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DatImage {
+    /// The secure area is encrypted, as read from a cartridge.
+    ///
+    /// This is the ROM data as loaded, since decrypted secure areas are
+    /// re-encrypted when loading.
+    Encrypted,
+    /// The secure area is decrypted, with a destroyed ID, see
+    /// [`NdsRom::decrypted_sha1`].
+    Decrypted,
+}
+
+/// Whether each field of a [`DatEntry`] matches a ROM.
+///
+/// Returned by [`NdsRom::verify_against`]. The hashes are `None` if they are
+/// not listed in the entry.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DatVerdict {
+    /// Whether the size matches.
+    pub size: bool,
+    /// Whether the CRC-32 matches.
+    pub crc32: bool,
+    /// Whether the MD5 digest matches.
+    pub md5: Option<bool>,
+    /// Whether the SHA-1 digest matches.
+    pub sha1: Option<bool>,
+}
+
+impl DatVerdict {
+    /// Returns `true` if every field listed in the entry matches.
+    pub fn is_match(&self) -> bool {
+        self.size && self.crc32 && self.md5 != Some(false) && self.sha1 != Some(false)
+    }
+}
+
+/// Hashes of a ROM image, cached by [`NdsRom`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ImageHashes {
+    pub(crate) size: u64,
+    pub(crate) crc32: u32,
+    pub(crate) md5: [u8; 16],
+    pub(crate) sha1: [u8; 20],
+}
+
+impl NdsRom {
+    /// Compares the ROM against its expected size and hashes from a DAT file,
+    /// listing images of the given form.
+    ///
+    /// The ROM data is hashed without the zero padding added when loading.
+    /// The hashes are computed once, and cached like [`checksums`].
+    ///
+    /// [`checksums`]: NdsRom::checksums
+    pub fn verify_against(&self, expected: &DatEntry, image: DatImage) -> DatVerdict {
+        let hashes = self.image_hashes(image);
+
+        DatVerdict {
+            size: hashes.size == expected.size,
+            crc32: hashes.crc32 == expected.crc32,
+            md5: expected.md5.map(|expected| hashes.md5 == expected),
+            sha1: expected.sha1.map(|expected| hashes.sha1 == expected),
+        }
+    }
+
+    /// Returns the cached hashes of the ROM image.
+    pub(crate) fn image_hashes(&self, image: DatImage) -> &ImageHashes {
+        self.image_hashes[image as usize].get_or_init(|| {
+            let mut size = 0;
+            let mut crc32 = Crc32::new();
+            let mut md5 = Md5::new();
+            let mut sha1 = Sha1::new();
+
+            self.hash_image(image, |chunk| {
+                size += chunk.len() as u64;
+                crc32.update(chunk);
+                md5.update(chunk);
+                sha1.update(chunk);
+            });
+
+            ImageHashes {
+                size,
+                crc32: !crc32.finish(),
+                md5: md5.finish(),
+                sha1: sha1.finish(),
+            }
+        })
+    }
+
+    /// Passes the ROM image to `update`, in order.
+    ///
+    /// The decrypted image has the secure area in its canonical decrypted
+    /// form, see [`NdsRom::decrypted_sha1`].
+    fn hash_image<F: FnMut(&[u8])>(&self, image: DatImage, mut update: F) {
+        // The encrypted part of the secure area.
+        const ENCRYPTED_SIZE: usize = 0x800;
+
+        let data = &self.rom[..self.data_size.min(self.rom.len())];
+        let start = self.header.arm9_rom_offset as usize;
+
+        let secure_area = match (image, self.secure_area()) {
+            (DatImage::Decrypted, Some(secure_area)) if start < data.len() => secure_area,
+            _ => return update(data),
+        };
+
+        let end = (start + secure_area.len()).min(data.len());
+        let mut secure_area = data[start..end].to_vec();
+
+        match SecureAreaId::of(&secure_area) {
+            // Unencrypted homebrew code is also classed as encrypted, and is
+            // hashed as is.
+            SecureAreaId::Encrypted if secure_area.len() >= ENCRYPTED_SIZE => {
+                Key1::try_decrypt_secure_area(&mut secure_area, self.header.game_code());
+            }
+            SecureAreaId::Raw => secure_area[0..8].copy_from_slice(&DESTROYED_ID),
+            _ => {}
+        }
+
+        update(&data[..start]);
+        update(&secure_area);
+        update(&data[end..]);
+    }
+}
@@ -0,0 +1,205 @@
+use std::mem;
+
+/// DSi extended ROM header.
+///
+/// Follows the NDS header fields, from `0x180` up to `0x1000` in ROM. Only
+/// present on DSi enhanced and DSi exclusive ROMs.
+///
+/// # Sources
+///
+/// \[1\]: <https://problemkaputt.de/gbatek.htm#dsicartridgeheader>
+///
+/// \[2\]: <https://dsibrew.org/wiki/DSi_cartridge_header>
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct DsiHeader {
+    /// Global MBK1..MBK5 WRAM slot settings.
+    pub global_mbk_settings: [u32; 5], // 0x180
+    /// Local ARM9 MBK6..MBK8 WRAM area settings.
+    pub arm9_mbk_settings: [u32; 3], // 0x194
+    /// Local ARM7 MBK6..MBK8 WRAM area settings.
+    pub arm7_mbk_settings: [u32; 3], // 0x1A0
+    /// Global MBK9 WRAM slot write protect setting.
+    pub mbk9_setting: [u8; 3], // 0x1AC
+    /// Global WRAMCNT setting.
+    ///
+    /// Usually `0x03`.
+    pub wramcnt_setting: u8, // 0x1AF
+    /// Region flags.
+    ///
+    /// - Bit0: Japan
+    /// - Bit1: USA
+    /// - Bit2: Europe
+    /// - Bit3: Australia
+    /// - Bit4: China
+    /// - Bit5: Korea
+    ///
+    /// `0xFFFFFFFF` for region free.
+    pub region_flags: u32, // 0x1B0
+    /// Access control (AES key select).
+    pub access_control: u32, // 0x1B4
+    /// ARM7 SCFG_EXT7 setting.
+    pub arm7_scfg_ext7: u32, // 0x1B8
+    /// Reserved, zero filled.
+    reserved1: [u8; 3], // 0x1BC
+    /// Flags.
+    ///
+    /// Usually `0x01`.
+    pub flags: u8, // 0x1BF
+
+    /// ARM9i ROM offset.
+    pub arm9i_rom_offset: u32, // 0x1C0
+    /// Reserved, zero filled.
+    reserved2: u32, // 0x1C4
+    /// ARM9i RAM address.
+    pub arm9i_ram_address: u32, // 0x1C8
+    /// ARM9i code size.
+    pub arm9i_size: u32, // 0x1CC
+
+    /// ARM7i ROM offset.
+    pub arm7i_rom_offset: u32, // 0x1D0
+    /// SD/MMC device list ARM7 RAM address.
+    pub device_list_address: u32, // 0x1D4
+    /// ARM7i RAM address.
+    pub arm7i_ram_address: u32, // 0x1D8
+    /// ARM7i code size.
+    pub arm7i_size: u32, // 0x1DC
+
+    /// Digest NTR region offset.
+    pub digest_ntr_offset: u32, // 0x1E0
+    /// Digest NTR region size.
+    pub digest_ntr_size: u32, // 0x1E4
+    /// Digest TWL region offset.
+    pub digest_twl_offset: u32, // 0x1E8
+    /// Digest TWL region size.
+    pub digest_twl_size: u32, // 0x1EC
+    /// Digest sector hash table offset.
+    pub digest_sector_table_offset: u32, // 0x1F0
+    /// Digest sector hash table size.
+    pub digest_sector_table_size: u32, // 0x1F4
+    /// Digest block hash table offset.
+    pub digest_block_table_offset: u32, // 0x1F8
+    /// Digest block hash table size.
+    pub digest_block_table_size: u32, // 0x1FC
+    /// Digest sector size.
+    ///
+    /// Usually `0x400`.
+    pub digest_sector_size: u32, // 0x200
+    /// Digest sectors per block.
+    ///
+    /// Usually `0x20`.
+    pub digest_block_sector_count: u32, // 0x204
+
+    /// Banner size.
+    ///
+    /// Usually `0x23C0` for DSi, or `0xA00` for NDS.
+    pub banner_size: u32, // 0x208
+    /// Unknown, includes the sizes of the SD/MMC `shared2` files.
+    unknown1: [u8; 20], // 0x20C
+
+    /// Modcrypt area 1 offset.
+    pub modcrypt1_offset: u32, // 0x220
+    /// Modcrypt area 1 size.
+    pub modcrypt1_size: u32, // 0x224
+    /// Modcrypt area 2 offset.
+    pub modcrypt2_offset: u32, // 0x228
+    /// Modcrypt area 2 size.
+    pub modcrypt2_size: u32, // 0x22C
+
+    /// Title ID.
+    pub title_id: u64, // 0x230
+    /// DSiWare `public.sav` size.
+    ///
+    /// In bytes, `0x0000` for none.
+    pub public_save_size: u32, // 0x238
+    /// DSiWare `private.sav` size.
+    ///
+    /// In bytes, `0x0000` for none.
+    pub private_save_size: u32, // 0x23C
+
+    /// Reserved, zero filled.
+    reserved3: [u8; 176], // 0x240
+
+    /// Parental control age ratings.
+    pub age_ratings: [u8; 16], // 0x2F0
+
+    /// SHA1-HMAC of the ARM9 code, with encrypted secure area.
+    pub arm9_hmac: [u8; 20], // 0x300
+    /// SHA1-HMAC of the ARM7 code.
+    pub arm7_hmac: [u8; 20], // 0x314
+    /// SHA1-HMAC of the digest block hash table.
+    pub digest_hmac: [u8; 20], // 0x328
+    /// SHA1-HMAC of the banner.
+    pub banner_hmac: [u8; 20], // 0x33C
+    /// SHA1-HMAC of the decrypted ARM9i code.
+    pub arm9i_hmac: [u8; 20], // 0x350
+    /// SHA1-HMAC of the decrypted ARM7i code.
+    pub arm7i_hmac: [u8; 20], // 0x364
+    /// Reserved, zero filled.
+    reserved4: [u8; 40], // 0x378
+    /// SHA1-HMAC of the ARM9 code, without the secure area.
+    pub arm9_no_secure_area_hmac: [u8; 20], // 0x3A0
+
+    /// Reserved, zero filled.
+    reserved5: [u8; 2636], // 0x3B4
+    /// Reserved, zero filled.
+    ///
+    /// Used to pass arguments to debug builds.
+    reserved6: [u8; 384], // 0xE00
+
+    /// RSA-SHA1 signature of `0x000..=0xDFF`.
+    pub rsa_signature: [u8; 128], // 0xF80
+}
+
+static_assert!(DsiHeader::OFFSET + DsiHeader::SIZE == 0x1000);
+static_assert!(mem::offset_of!(DsiHeader, region_flags) == 0x1B0 - DsiHeader::OFFSET);
+static_assert!(mem::offset_of!(DsiHeader, arm9i_rom_offset) == 0x1C0 - DsiHeader::OFFSET);
+static_assert!(mem::offset_of!(DsiHeader, digest_sector_size) == 0x200 - DsiHeader::OFFSET);
+static_assert!(mem::offset_of!(DsiHeader, modcrypt1_offset) == 0x220 - DsiHeader::OFFSET);
+static_assert!(mem::offset_of!(DsiHeader, title_id) == 0x230 - DsiHeader::OFFSET);
+static_assert!(mem::offset_of!(DsiHeader, public_save_size) == 0x238 - DsiHeader::OFFSET);
+static_assert!(mem::offset_of!(DsiHeader, age_ratings) == 0x2F0 - DsiHeader::OFFSET);
+static_assert!(mem::offset_of!(DsiHeader, arm9_hmac) == 0x300 - DsiHeader::OFFSET);
+static_assert!(mem::offset_of!(DsiHeader, reserved5) == 0x3B4 - DsiHeader::OFFSET);
+static_assert!(mem::offset_of!(DsiHeader, rsa_signature) == 0xF80 - DsiHeader::OFFSET);
+
+impl DsiHeader {
+    /// The offset of the extended header in ROM.
+    pub const OFFSET: usize = 0x180;
+
+    /// The size of the extended header in bytes.
+    pub const SIZE: usize = mem::size_of::<Self>();
+
+    pub(crate) fn read(rom: &[u8]) -> DsiHeader {
+        let bytes: [u8; DsiHeader::SIZE] = rom
+            [DsiHeader::OFFSET..(DsiHeader::OFFSET + DsiHeader::SIZE)]
+            .try_into()
+            .unwrap();
+
+        #[cfg(target_endian = "big")]
+        panic!("big-endian targets are not yet supported");
+
+        // SAFETY: `DsiHeader` is `repr(C)` with no padding, and any bit pattern
+        // is valid for its fields.
+        #[cfg(target_endian = "little")]
+        unsafe {
+            mem::transmute(bytes)
+        }
+    }
+
+    /// Returns the size of the DSiWare `public.sav` file in bytes.
+    ///
+    /// Returns `0` if the title has no public save.
+    #[inline]
+    pub fn public_save_size(&self) -> usize {
+        self.public_save_size as usize
+    }
+
+    /// Returns the size of the DSiWare `private.sav` file in bytes.
+    ///
+    /// Returns `0` if the title has no private save.
+    #[inline]
+    pub fn private_save_size(&self) -> usize {
+        self.private_save_size as usize
+    }
+}
@@ -1,3 +1,5 @@
+use std::num::NonZeroU8;
+
 use byteorder::{ByteOrder, LittleEndian};
 
 const KEY_DATA_LEN: usize = 0x412;
@@ -140,11 +142,14 @@ static KEY_DATA: [u32; KEY_DATA_LEN] = [
 ];
 
 // Magic value for secure area ID.
-const ENCRY_OBJ: [u8; 8] = *b"encryObj";
+pub(crate) const ENCRY_OBJ: [u8; 8] = *b"encryObj";
 // Magic value for destroyed secure area ID.
 //
 // This is `0xE7FFDEFF` in little-endian repeated twice.
-const DESTROYED_ID: [u8; 8] = [0xFF, 0xDE, 0xFF, 0xE7, 0xFF, 0xDE, 0xFF, 0xE7];
+pub(crate) const DESTROYED_ID: [u8; 8] = [0xFF, 0xDE, 0xFF, 0xE7, 0xFF, 0xDE, 0xFF, 0xE7];
+// Magic value for the secure area disable field of the header, before
+// encryption.
+pub(crate) const NM_MD_ONLY: [u8; 8] = *b"NmMdOnly";
 
 #[derive(Debug)]
 pub struct Key1 {
@@ -224,40 +229,45 @@ impl Key1 {
         self.expand_key(key);
     }
 
-    /// Initialise KEY1 with level 1.
-    pub fn init1(game_code: u32) -> Key1 {
+    /// Initialise KEY1 with the given level.
+    ///
+    /// Each level applies the keycode once more. From level 3 onwards, the
+    /// keycode is modified before each application.
+    pub fn init(game_code: u32, level: NonZeroU8) -> Key1 {
         let mut key1 = Key1 { key_buf: KEY_DATA };
         let mut key = [game_code, game_code >> 1, game_code << 1];
 
-        key1.apply_keycode(&mut key);
+        for level in 1..=level.get() {
+            if level >= 3 {
+                key[1] <<= 1;
+                key[2] >>= 1;
+            }
+            key1.apply_keycode(&mut key);
+        }
 
         key1
     }
 
+    /// Initialise KEY1 with level 1.
+    ///
+    /// Used for encrypting KEY1 commands.
+    pub fn init1(game_code: u32) -> Key1 {
+        Key1::init(game_code, NonZeroU8::new(1).unwrap())
+    }
+
     /// Initialise KEY1 with level 2.
+    ///
+    /// Used for encrypting the secure area ID and the secure area disable
+    /// field of the header.
     pub fn init2(game_code: u32) -> Key1 {
-        let mut key1 = Key1 { key_buf: KEY_DATA };
-        let mut key = [game_code, game_code >> 1, game_code << 1];
-
-        key1.apply_keycode(&mut key);
-        key1.apply_keycode(&mut key);
-
-        key1
+        Key1::init(game_code, NonZeroU8::new(2).unwrap())
     }
 
     /// Initialise KEY1 with level 3.
+    ///
+    /// Used for encrypting the secure area.
     pub fn init3(game_code: u32) -> Key1 {
-        let mut key1 = Key1 { key_buf: KEY_DATA };
-        let mut key = [game_code, game_code >> 1, game_code << 1];
-
-        key1.apply_keycode(&mut key);
-        key1.apply_keycode(&mut key);
-
-        key[1] <<= 1;
-        key[2] >>= 1;
-        key1.apply_keycode(&mut key);
-
-        key1
+        Key1::init(game_code, NonZeroU8::new(3).unwrap())
     }
 
     /// Encrypts a block of 8 bytes.
@@ -287,6 +297,10 @@ impl Key1 {
     }
 
     /// Encrypts the secure area of the ARM9 boot code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `secure_area` is shorter than `0x800` bytes.
     pub fn encrypt_secure_area(secure_area: &mut [u8], game_code: u32) {
         if cfg!(debug_assertions) && secure_area[0..8] != DESTROYED_ID {
             panic!("encryption failed");
@@ -304,7 +318,45 @@ impl Key1 {
     }
 
     /// Decrypts the secure area of the ARM9 boot code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `secure_area` is shorter than `0x800` bytes.
     pub fn decrypt_secure_area(secure_area: &mut [u8], game_code: u32) {
+        let key1 = Key1::decrypt_secure_area_id(secure_area, game_code);
+
+        if cfg!(debug_assertions) && secure_area[0..8] != ENCRY_OBJ {
+            panic!("decryption failed");
+        }
+
+        Key1::decrypt_secure_area_body(&key1, secure_area);
+    }
+
+    /// Decrypts the secure area of the ARM9 boot code, returning `true` if it
+    /// was decrypted.
+    ///
+    /// The secure area is left unchanged if the decrypted ID is not
+    /// `"encryObj"`, such as for unencrypted homebrew code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `secure_area` is shorter than `0x800` bytes.
+    pub fn try_decrypt_secure_area(secure_area: &mut [u8], game_code: u32) -> bool {
+        let mut id = [0; 8];
+        id.copy_from_slice(&secure_area[0..8]);
+
+        let key1 = Key1::decrypt_secure_area_id(&mut id, game_code);
+        if id != ENCRY_OBJ {
+            return false;
+        }
+
+        Key1::decrypt_secure_area_body(&key1, secure_area);
+        true
+    }
+
+    /// Decrypts the secure area ID in place, returning the key for the rest
+    /// of the secure area.
+    fn decrypt_secure_area_id(id: &mut [u8], game_code: u32) -> Key1 {
         let mut key1 = Key1 { key_buf: KEY_DATA };
         let mut key = [game_code, game_code >> 1, game_code << 1];
 
@@ -313,21 +365,21 @@ impl Key1 {
         key1.apply_keycode(&mut key);
         key1.apply_keycode(&mut key);
 
-        key1.decrypt_block(secure_area);
+        key1.decrypt_block(id);
 
         key[1] <<= 1;
         key[2] >>= 1;
         key1.apply_keycode(&mut key);
 
-        key1.decrypt_block(secure_area);
-
-        if cfg!(debug_assertions) && secure_area[0..8] != ENCRY_OBJ {
-            panic!("decryption failed");
-        }
+        key1.decrypt_block(id);
+        key1
+    }
 
+    /// Destroys the decrypted ID and decrypts the rest of the secure area.
+    fn decrypt_secure_area_body(key1: &Key1, secure_area: &mut [u8]) {
         secure_area[0..8].copy_from_slice(&DESTROYED_ID);
 
-        for i in 0x2..0x100 {
+        for i in 0x1..0x100 {
             key1.decrypt_block(&mut secure_area[8 * i..]);
         }
     }
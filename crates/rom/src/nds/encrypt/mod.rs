@@ -1,3 +1,93 @@
 mod key1;
 
 pub use self::key1::Key1;
+
+pub(crate) use self::key1::{DESTROYED_ID, NM_MD_ONLY};
+
+use self::key1::ENCRY_OBJ;
+
+/// The state of the secure area ID.
+///
+/// The first 8 bytes of the secure area contain the secure area ID, this ID
+/// is verified by the BIOS boot code, the ID value changes during the boot
+/// process.
+///
+/// # Sources
+///
+/// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgesecurearea>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SecureAreaId {
+    /// Raw ID `"encryObj"`, before encryption.
+    ///
+    /// Found in decrypted ROM images, with the rest of the secure area decrypted.
+    Raw,
+    /// Encrypted ID.
+    ///
+    /// Found in encrypted ROM images, as read from a cartridge.
+    Encrypted,
+    /// Destroyed ID `0xE7FFDEFF, 0xE7FFDEFF`, overwritten by the BIOS after
+    /// verifying the ID.
+    ///
+    /// Found in decrypted ROM images, with the rest of the secure area decrypted.
+    Destroyed,
+}
+
+impl SecureAreaId {
+    /// Determines the state of the ID at the start of a secure area.
+    pub fn of(secure_area: &[u8]) -> SecureAreaId {
+        match secure_area.get(0..8) {
+            Some(id) if id == ENCRY_OBJ => SecureAreaId::Raw,
+            Some(id) if id == DESTROYED_ID => SecureAreaId::Destroyed,
+            _ => SecureAreaId::Encrypted,
+        }
+    }
+}
+
+/// Re-encrypts the secure area of a decrypted ROM image in place, returning
+/// `true` if it was re-encrypted.
+///
+/// The secure area spans `arm9_rom_offset..0x8000`, and exists only if the
+/// offset is within `0x4000..0x8000`. Only a decrypted secure area with a
+/// destroyed ID is re-encrypted, a secure area that was fully destroyed by the
+/// BIOS (the first `0x800` bytes overwritten) cannot be recovered.
+///
+/// Nothing is done if the secure area extends past the end of `rom`, or is
+/// shorter than the `0x800` bytes encrypted.
+///
+/// # Sources
+///
+/// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgesecurearea>
+pub fn reencrypt_secure_area(rom: &mut [u8], game_code: u32, arm9_rom_offset: usize) -> bool {
+    // The encrypted part of the secure area.
+    const ENCRYPTED_SIZE: usize = 0x800;
+
+    if !(0x4000..0x8000).contains(&arm9_rom_offset) {
+        return false;
+    }
+    let Some(secure_area) = rom.get_mut(arm9_rom_offset..0x8000) else {
+        return false;
+    };
+
+    // If the decrypted ID matches, the BIOS overwrites only the ID with
+    // `0xE7FFDEFF` values. If it doesn't match, the whole encrypted part is
+    // overwritten, so the rest of the secure area is checked too.
+    if SecureAreaId::of(secure_area) != SecureAreaId::Destroyed
+        || secure_area.get(0x10..0x14) == Some(&DESTROYED_ID[..4])
+    {
+        return false;
+    }
+
+    if secure_area.len() < ENCRYPTED_SIZE {
+        log::warn!(
+            "secure area of {:#X} bytes is too small to re-encrypt",
+            secure_area.len()
+        );
+        return false;
+    }
+
+    log::debug!("re-encrypting ROM secure area");
+
+    Key1::encrypt_secure_area(secure_area, game_code);
+    true
+}
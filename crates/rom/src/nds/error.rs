@@ -0,0 +1,419 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use common::str::Utf16Error;
+
+use crate::nds::{BannerLanguage, VerifyIssue};
+
+/// An error in the contents of a ROM.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RomError {
+    /// The ROM is too small to contain a header.
+    TooSmall {
+        /// The size of the ROM in bytes.
+        size: usize,
+    },
+    /// The ROM is larger than [`MAX_ROM_SIZE`].
+    ///
+    /// [`MAX_ROM_SIZE`]: crate::nds::MAX_ROM_SIZE
+    TooLarge {
+        /// The size of the ROM in bytes.
+        size: u64,
+    },
+    /// The banner extends past the end of the ROM.
+    BannerOutOfBounds {
+        /// The banner offset from the header.
+        offset: u32,
+    },
+    /// The secure area extends past the end of the ROM.
+    SecureAreaOutOfBounds {
+        /// The ARM9 ROM offset from the header.
+        offset: u32,
+    },
+}
+
+impl fmt::Display for RomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RomError::TooSmall { size } => write!(f, "ROM size {} is too small", size),
+            RomError::TooLarge { size } => write!(f, "ROM size {} is too large", size),
+            RomError::BannerOutOfBounds { offset } => {
+                write!(f, "banner at offset {:#X} is out of bounds", offset)
+            }
+            RomError::SecureAreaOutOfBounds { offset } => {
+                write!(f, "secure area at offset {:#X} is out of bounds", offset)
+            }
+        }
+    }
+}
+
+impl Error for RomError {}
+
+/// An error parsing a standalone header.
+///
+/// Returned by [`NdsHeader::parse`].
+///
+/// [`NdsHeader::parse`]: crate::nds::NdsHeader::parse
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HeaderError {
+    /// The data is too small to contain a header.
+    TooSmall {
+        /// The size of the data in bytes.
+        size: usize,
+    },
+}
+
+impl fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeaderError::TooSmall { size } => write!(f, "header size {} is too small", size),
+        }
+    }
+}
+
+impl Error for HeaderError {}
+
+/// An error editing a header string field.
+///
+/// Returned by [`NdsHeader::set_game_title`] and [`NdsHeader::set_game_code`].
+///
+/// [`NdsHeader::set_game_title`]: crate::nds::NdsHeader::set_game_title
+/// [`NdsHeader::set_game_code`]: crate::nds::NdsHeader::set_game_code
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HeaderEditError {
+    /// The string contains a character that is not printable ASCII.
+    InvalidChar {
+        /// The byte index of the character.
+        index: usize,
+    },
+    /// The string is too long for the field.
+    TooLong {
+        /// The length of the string in bytes.
+        len: usize,
+        /// The capacity of the field in bytes.
+        capacity: usize,
+    },
+    /// The string is not the exact length of the field.
+    WrongLength {
+        /// The length of the string in bytes.
+        len: usize,
+        /// The required length in bytes.
+        expected: usize,
+    },
+}
+
+impl fmt::Display for HeaderEditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeaderEditError::InvalidChar { index } => {
+                write!(f, "invalid character at index {}", index)
+            }
+            HeaderEditError::TooLong { len, capacity } => {
+                write!(f, "length {} exceeds capacity {}", len, capacity)
+            }
+            HeaderEditError::WrongLength { len, expected } => {
+                write!(f, "length {} is not {}", len, expected)
+            }
+        }
+    }
+}
+
+impl Error for HeaderEditError {}
+
+/// An error parsing a standalone banner.
+///
+/// Returned by [`NdsBanner::parse`].
+///
+/// [`NdsBanner::parse`]: crate::nds::NdsBanner::parse
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BannerError {
+    /// The data is too small for the banner version.
+    TooSmall {
+        /// The size of the data in bytes.
+        size: usize,
+        /// The size required in bytes.
+        expected: usize,
+    },
+    /// The banner version is unknown.
+    UnknownVersion(u16),
+}
+
+impl fmt::Display for BannerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BannerError::TooSmall { size, expected } => {
+                write!(
+                    f,
+                    "banner size {} is too small, expected {}",
+                    size, expected
+                )
+            }
+            BannerError::UnknownVersion(version) => {
+                write!(f, "unknown banner version {:#06X}", version)
+            }
+        }
+    }
+}
+
+impl Error for BannerError {}
+
+/// An error editing the banner of a ROM.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BannerEditError {
+    /// The ROM has no banner.
+    NoBanner,
+    /// The banner version does not have a title in the language.
+    UnsupportedLanguage(BannerLanguage),
+    /// The title is too long.
+    TooLong(Utf16Error),
+    /// The banner extends past the end of the ROM.
+    OutOfBounds {
+        /// The banner offset from the header.
+        offset: u32,
+    },
+}
+
+impl fmt::Display for BannerEditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BannerEditError::NoBanner => f.write_str("ROM has no banner"),
+            BannerEditError::UnsupportedLanguage(lang) => {
+                write!(f, "banner version has no {:?} title", lang)
+            }
+            BannerEditError::TooLong(err) => write!(f, "title is too long: {}", err),
+            BannerEditError::OutOfBounds { offset } => {
+                write!(f, "banner at offset {:#X} is out of bounds", offset)
+            }
+        }
+    }
+}
+
+impl Error for BannerEditError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BannerEditError::TooLong(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Utf16Error> for BannerEditError {
+    fn from(err: Utf16Error) -> Self {
+        BannerEditError::TooLong(err)
+    }
+}
+
+/// A problem found by [`NdsRom::try_load`].
+///
+/// [`NdsRom::try_load`]: crate::nds::NdsRom::try_load
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoadDiagnostic {
+    /// A recoverable error, the affected part of the ROM was skipped.
+    ///
+    /// - [`RomError::BannerOutOfBounds`]: the banner is not loaded.
+    /// - [`RomError::SecureAreaOutOfBounds`]: the secure area is not decrypted.
+    Error(RomError),
+    /// The banner is truncated by the end of the ROM data, and zero padded.
+    BannerTruncated {
+        /// The banner offset from the header.
+        offset: u32,
+    },
+    /// An integrity problem, as found by [`NdsRom::verify`].
+    ///
+    /// [`NdsRom::verify`]: crate::nds::NdsRom::verify
+    Issue(VerifyIssue),
+}
+
+impl fmt::Display for LoadDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadDiagnostic::Error(err) => err.fmt(f),
+            LoadDiagnostic::BannerTruncated { offset } => {
+                write!(f, "banner at offset {:#X} is truncated", offset)
+            }
+            LoadDiagnostic::Issue(issue) => issue.fmt(f),
+        }
+    }
+}
+
+/// An error opening a ROM file.
+#[derive(Debug)]
+pub enum OpenError {
+    /// The file could not be read.
+    Io(io::Error),
+    /// The file is not a valid ROM.
+    Rom(RomError),
+    /// The ROM could not be extracted from an archive.
+    #[cfg(feature = "archives")]
+    Archive(ArchiveError),
+}
+
+impl fmt::Display for OpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenError::Io(err) => err.fmt(f),
+            OpenError::Rom(err) => err.fmt(f),
+            #[cfg(feature = "archives")]
+            OpenError::Archive(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for OpenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            OpenError::Io(err) => Some(err),
+            OpenError::Rom(err) => Some(err),
+            #[cfg(feature = "archives")]
+            OpenError::Archive(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for OpenError {
+    fn from(err: io::Error) -> Self {
+        OpenError::Io(err)
+    }
+}
+
+impl From<RomError> for OpenError {
+    fn from(err: RomError) -> Self {
+        OpenError::Rom(err)
+    }
+}
+
+#[cfg(feature = "archives")]
+impl From<ArchiveError> for OpenError {
+    fn from(err: ArchiveError) -> Self {
+        OpenError::Archive(err)
+    }
+}
+
+/// An error extracting a ROM from an archive.
+#[cfg(feature = "archives")]
+#[derive(Debug)]
+pub enum ArchiveError {
+    /// The archive does not contain a ROM.
+    NoRom,
+    /// The archive contains more than one ROM.
+    MultipleRoms,
+    /// The `.zip` archive could not be read.
+    Zip(zip::result::ZipError),
+    /// The `.7z` archive could not be read.
+    SevenZ(sevenz_rust::Error),
+}
+
+#[cfg(feature = "archives")]
+impl fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArchiveError::NoRom => f.write_str("archive does not contain a ROM"),
+            ArchiveError::MultipleRoms => f.write_str("archive contains multiple ROMs"),
+            ArchiveError::Zip(err) => err.fmt(f),
+            ArchiveError::SevenZ(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "archives")]
+impl Error for ArchiveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ArchiveError::NoRom | ArchiveError::MultipleRoms => None,
+            ArchiveError::Zip(err) => Some(err),
+            ArchiveError::SevenZ(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "archives")]
+impl From<zip::result::ZipError> for ArchiveError {
+    fn from(err: zip::result::ZipError) -> Self {
+        ArchiveError::Zip(err)
+    }
+}
+
+#[cfg(feature = "archives")]
+impl From<sevenz_rust::Error> for ArchiveError {
+    fn from(err: sevenz_rust::Error) -> Self {
+        ArchiveError::SevenZ(err)
+    }
+}
+
+/// An error reading the filesystem of a ROM.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FilesystemError {
+    /// The file name table (FNT) extends past the end of the ROM.
+    OutOfBounds,
+    /// The file name table (FNT) is implausible, it may be encrypted or
+    /// obfuscated by a protection scheme.
+    Obfuscated,
+}
+
+impl fmt::Display for FilesystemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilesystemError::OutOfBounds => f.write_str("file name table is out of bounds"),
+            FilesystemError::Obfuscated => f.write_str("file name table is obfuscated"),
+        }
+    }
+}
+
+impl Error for FilesystemError {}
+
+/// An error replacing a file in a ROM.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReplaceError {
+    /// The filesystem could not be read.
+    Filesystem(FilesystemError),
+    /// No file exists at the given path.
+    NotFound,
+    /// The file allocation table (FAT) entry for the file is invalid.
+    InvalidFatEntry {
+        /// The file ID.
+        id: u16,
+    },
+    /// The replacement data is not the same size as the existing file.
+    ///
+    /// Files can only be replaced in place if the size is unchanged, otherwise
+    /// the ROM must be rebuilt.
+    SizeMismatch {
+        /// The size of the existing file.
+        expected: usize,
+        /// The size of the replacement data.
+        actual: usize,
+    },
+}
+
+impl fmt::Display for ReplaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplaceError::Filesystem(err) => err.fmt(f),
+            ReplaceError::NotFound => f.write_str("file not found"),
+            ReplaceError::InvalidFatEntry { id } => {
+                write!(f, "invalid FAT entry for file ID {:#06X}", id)
+            }
+            ReplaceError::SizeMismatch { expected, actual } => write!(
+                f,
+                "replacement size {} does not match file size {}, the ROM must be rebuilt",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl Error for ReplaceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReplaceError::Filesystem(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<FilesystemError> for ReplaceError {
+    fn from(err: FilesystemError) -> Self {
+        ReplaceError::Filesystem(err)
+    }
+}
@@ -0,0 +1,36 @@
+use std::path::Path;
+
+/// The ROM format expected from a file extension.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RomFormat {
+    /// `.nds` or `.ids` (iQue), an NDS ROM, which may be DSi enhanced.
+    Nds,
+    /// `.dsi`, a DSi enhanced or DSi exclusive ROM.
+    Dsi,
+    /// `.srl`, an SDK build which may be an NDS or DSi ROM.
+    Srl,
+}
+
+impl RomFormat {
+    /// Returns the format expected from a file extension, ignoring case.
+    ///
+    /// Returns `None` if the extension is not a ROM extension.
+    pub fn from_extension(ext: &str) -> Option<RomFormat> {
+        match ext.to_ascii_lowercase().as_str() {
+            "nds" | "ids" => Some(RomFormat::Nds),
+            "dsi" => Some(RomFormat::Dsi),
+            "srl" => Some(RomFormat::Srl),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the ROM format expected from the extension of `path`.
+///
+/// Returns `None` if the path does not have a ROM extension.
+pub fn detect_format<P: AsRef<Path>>(path: P) -> Option<RomFormat> {
+    path.as_ref()
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(RomFormat::from_extension)
+}
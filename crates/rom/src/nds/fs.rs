@@ -0,0 +1,231 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::nds::error::FilesystemError;
+
+/// The directory ID of the root directory.
+pub const ROOT_DIR_ID: u16 = 0xF000;
+
+/// NDS ROM filesystem.
+///
+/// Directories are read lazily from the file name table (FNT), only the
+/// subtable of a directory is parsed when its entries are iterated.
+///
+/// # FNT layout
+///
+/// The FNT starts with the main table, an 8 byte entry for each directory:
+///
+/// - `0x00` = offset of the subtable (relative to the FNT)
+/// - `0x04` = ID of the first file in the subtable
+/// - `0x06` = total number of directories (root), or parent directory ID
+///
+/// Each subtable is a sequence of entries, starting with a type/length byte:
+///
+/// - `0x01..=0x7F` = file entry, with a name of length `N`
+/// - `0x81..=0xFF` = subdirectory entry, with a name of length `N - 0x80`,
+///   followed by the subdirectory ID (`0xF001..=0xFFFF`)
+/// - `0x00` = end of subtable
+/// - `0x80` = reserved
+///
+/// # Sources
+///
+/// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgenitroromandnitroarcfilesystems>
+#[derive(Clone, Copy, Debug)]
+pub struct Filesystem<'a> {
+    fnt: &'a [u8],
+}
+
+/// A directory in the filesystem.
+#[derive(Clone, Copy, Debug)]
+pub struct Directory<'a> {
+    fs: Filesystem<'a>,
+    id: u16,
+}
+
+/// An entry in a directory.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Entry<'a> {
+    /// A file, with its name and file ID.
+    File(&'a [u8], u16),
+    /// A subdirectory, with its name and directory ID.
+    Dir(&'a [u8], u16),
+}
+
+/// An iterator over the entries in a directory.
+#[derive(Clone, Debug)]
+pub struct Entries<'a> {
+    subtable: &'a [u8],
+    file_id: u16,
+}
+
+impl<'a> Filesystem<'a> {
+    /// Creates a filesystem from the bytes of a file name table (FNT).
+    ///
+    /// The root directory entry is checked for plausibility, an empty FNT is
+    /// treated as an empty filesystem.
+    pub fn new(fnt: &'a [u8]) -> Result<Filesystem<'a>, FilesystemError> {
+        let fs = Filesystem { fnt };
+        if fnt.is_empty() {
+            return Ok(fs);
+        }
+
+        let root = fs
+            .main_entry(ROOT_DIR_ID)
+            .ok_or(FilesystemError::Obfuscated)?;
+        let subtable_offset = LittleEndian::read_u32(&root[0..4]) as usize;
+        let main_table_size = (fs.dir_count() as usize) * 8;
+
+        // There are at most `0x1000` directory IDs, and the subtables must
+        // follow the main table.
+        if fs.dir_count() == 0
+            || fs.dir_count() > 0x1000
+            || main_table_size > subtable_offset
+            || subtable_offset >= fnt.len()
+        {
+            return Err(FilesystemError::Obfuscated);
+        }
+
+        Ok(fs)
+    }
+
+    /// Returns the root directory.
+    pub fn root(&self) -> Directory<'a> {
+        Directory {
+            fs: *self,
+            id: ROOT_DIR_ID,
+        }
+    }
+
+    /// Returns the directory with the given ID, if it exists.
+    pub fn dir(&self, id: u16) -> Option<Directory<'a>> {
+        if id >= ROOT_DIR_ID && id - ROOT_DIR_ID < self.dir_count() {
+            Some(Directory { fs: *self, id })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the total number of directories, including the root directory.
+    pub fn dir_count(&self) -> u16 {
+        match self.main_entry(ROOT_DIR_ID) {
+            Some(entry) => LittleEndian::read_u16(&entry[6..8]),
+            None => 0,
+        }
+    }
+
+    /// Looks up an entry by its `/` separated path, relative to the root directory.
+    pub fn find(&self, path: &str) -> Option<Entry<'a>> {
+        let mut dir = self.root();
+        let mut found = None;
+
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            // Only directories can be descended into.
+            if let Some(Entry::File(..)) = found {
+                return None;
+            }
+            if let Some(Entry::Dir(_, id)) = found {
+                dir = self.dir(id)?;
+            }
+
+            found = Some(
+                dir.entries()
+                    .find(|entry| entry.name() == name.as_bytes())?,
+            );
+        }
+
+        found
+    }
+
+    fn main_entry(&self, id: u16) -> Option<&'a [u8]> {
+        let offset = (id.checked_sub(ROOT_DIR_ID)? as usize) * 8;
+        self.fnt.get(offset..(offset + 8))
+    }
+}
+
+impl<'a> Directory<'a> {
+    /// Returns the directory ID.
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    /// Returns the parent directory, or `None` for the root directory.
+    pub fn parent(&self) -> Option<Directory<'a>> {
+        if self.id == ROOT_DIR_ID {
+            return None;
+        }
+
+        let entry = self.fs.main_entry(self.id)?;
+        self.fs.dir(LittleEndian::read_u16(&entry[6..8]))
+    }
+
+    /// Returns an iterator over the entries in the directory.
+    ///
+    /// Subdirectories are not descended into.
+    pub fn entries(&self) -> Entries<'a> {
+        let (subtable, file_id) = match self.fs.main_entry(self.id) {
+            Some(entry) => {
+                let offset = LittleEndian::read_u32(&entry[0..4]) as usize;
+                let file_id = LittleEndian::read_u16(&entry[4..6]);
+                (self.fs.fnt.get(offset..).unwrap_or(&[]), file_id)
+            }
+            None => (&[][..], 0),
+        };
+
+        Entries { subtable, file_id }
+    }
+}
+
+impl<'a> Entry<'a> {
+    /// Returns the name of the entry.
+    ///
+    /// Names are usually ASCII, but this is not guaranteed.
+    pub fn name(&self) -> &'a [u8] {
+        match *self {
+            Entry::File(name, _) | Entry::Dir(name, _) => name,
+        }
+    }
+
+    /// Returns the file ID or directory ID of the entry.
+    pub fn id(&self) -> u16 {
+        match *self {
+            Entry::File(_, id) | Entry::Dir(_, id) => id,
+        }
+    }
+
+    /// Returns `true` if the entry is a directory.
+    pub fn is_dir(&self) -> bool {
+        matches!(self, Entry::Dir(..))
+    }
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = Entry<'a>;
+
+    fn next(&mut self) -> Option<Entry<'a>> {
+        let (&kind, rest) = self.subtable.split_first()?;
+
+        let len = (kind & 0x7F) as usize;
+        // A zero length marks the end of the subtable (`0x00`), or is reserved (`0x80`).
+        if len == 0 || rest.len() < len {
+            self.subtable = &[];
+            return None;
+        }
+        let (name, rest) = rest.split_at(len);
+
+        if kind & 0x80 == 0 {
+            let id = self.file_id;
+            self.file_id = self.file_id.wrapping_add(1);
+            self.subtable = rest;
+
+            Some(Entry::File(name, id))
+        } else {
+            if rest.len() < 2 {
+                self.subtable = &[];
+                return None;
+            }
+            let id = LittleEndian::read_u16(&rest[0..2]);
+            self.subtable = &rest[2..];
+
+            Some(Entry::Dir(name, id))
+        }
+    }
+}
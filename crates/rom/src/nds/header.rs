@@ -1,11 +1,32 @@
 use std::mem;
+use std::ops::Range;
 
 use common::str::Ascii;
 use common::util::crc;
 
-use crate::nds::info::{MAKERS, REGIONS};
+use crate::nds::addr::{RamAddress, RomOffset};
+use crate::nds::banner::NdsBanner;
+use crate::nds::command::CommandSettings;
+use crate::nds::encrypt::{Key1, NM_MD_ONLY};
+use crate::nds::info::{Region, MAKERS, REGIONS};
+use crate::nds::{HeaderEditError, HeaderError};
 
-// TODO: Add proper support for DSi headers.
+/// The genuine Nintendo logo.
+///
+/// Compressed bitmap, verified by the firmware.
+#[rustfmt::skip]
+pub static NINTENDO_LOGO: [u8; 156] = [
+    0x24, 0xFF, 0xAE, 0x51, 0x69, 0x9A, 0xA2, 0x21, 0x3D, 0x84, 0x82, 0x0A, 0x84, 0xE4, 0x09, 0xAD,
+    0x11, 0x24, 0x8B, 0x98, 0xC0, 0x81, 0x7F, 0x21, 0xA3, 0x52, 0xBE, 0x19, 0x93, 0x09, 0xCE, 0x20,
+    0x10, 0x46, 0x4A, 0x4A, 0xF8, 0x27, 0x31, 0xEC, 0x58, 0xC7, 0xE8, 0x33, 0x82, 0xE3, 0xCE, 0xBF,
+    0x85, 0xF4, 0xDF, 0x94, 0xCE, 0x4B, 0x09, 0xC1, 0x94, 0x56, 0x8A, 0xC0, 0x13, 0x72, 0xA7, 0xFC,
+    0x9F, 0x84, 0x4D, 0x73, 0xA3, 0xCA, 0x9A, 0x61, 0x58, 0x97, 0xA3, 0x27, 0xFC, 0x03, 0x98, 0x76,
+    0x23, 0x1D, 0xC7, 0x61, 0x03, 0x04, 0xAE, 0x56, 0xBF, 0x38, 0x84, 0x00, 0x40, 0xA7, 0x0E, 0xFD,
+    0xFF, 0x52, 0xFE, 0x03, 0x6F, 0x95, 0x30, 0xF1, 0x97, 0xFB, 0xC0, 0x85, 0x60, 0xD6, 0x80, 0x25,
+    0xA9, 0x63, 0xBE, 0x03, 0x01, 0x4E, 0x38, 0xE2, 0xF9, 0xA2, 0x34, 0xFF, 0xBB, 0x3E, 0x03, 0x44,
+    0x78, 0x00, 0x90, 0xCB, 0x88, 0x11, 0x3A, 0x94, 0x65, 0xC0, 0x7C, 0x63, 0x87, 0xF0, 0x3C, 0xAF,
+    0xD6, 0x25, 0xE4, 0x8B, 0x38, 0x0A, 0xAC, 0x72, 0x21, 0xD4, 0xF8, 0x07,
+];
 
 /// NDS ROM header.
 ///
@@ -45,7 +66,7 @@ pub struct NdsHeader {
     pub device_capacity: u8, // 0x014
     /// Reserved, zero filled.
     reserved1: [u8; 7], // 0x015
-    // TODO: Handle DSi header info.
+    // DSi flags, see `NdsHeader::twl_capabilities`.
     dsi_flags: u8, // 0x01C
     /// NDS region.
     ///
@@ -150,7 +171,8 @@ pub struct NdsHeader {
 
     /// Total ROM size.
     ///
-    /// Remaining/Unused bytes usually `0xFF` padded.
+    /// Remaining/Unused bytes usually `0xFF` padded. See
+    /// [`used_rom_size`](NdsHeader::used_rom_size).
     pub rom_size: u32, // 0x080
     /// ROM header size.
     pub header_size: u32, // 0x084
@@ -216,35 +238,311 @@ pub struct NdsHeader {
 }
 
 static_assert!(NdsHeader::SIZE == 0x200);
+static_assert!(mem::offset_of!(NdsHeader, game_code) == 0x00C);
+static_assert!(mem::offset_of!(NdsHeader, unit_code) == 0x012);
+static_assert!(mem::offset_of!(NdsHeader, dsi_flags) == 0x01C);
+static_assert!(mem::offset_of!(NdsHeader, arm9_rom_offset) == 0x020);
+static_assert!(mem::offset_of!(NdsHeader, arm7_rom_offset) == 0x030);
+static_assert!(mem::offset_of!(NdsHeader, fnt_offset) == 0x040);
+static_assert!(mem::offset_of!(NdsHeader, normal_command_settings) == 0x060);
+static_assert!(mem::offset_of!(NdsHeader, banner_offset) == 0x068);
+static_assert!(mem::offset_of!(NdsHeader, secure_area_crc16) == 0x06C);
+static_assert!(mem::offset_of!(NdsHeader, secure_area_disable) == 0x078);
+static_assert!(mem::offset_of!(NdsHeader, rom_size) == 0x080);
+static_assert!(mem::offset_of!(NdsHeader, nand_rom_end) == 0x094);
+static_assert!(mem::offset_of!(NdsHeader, nintendo_logo) == 0x0C0);
+static_assert!(mem::offset_of!(NdsHeader, nintendo_logo_crc16) == 0x15C);
+static_assert!(mem::offset_of!(NdsHeader, header_crc16) == 0x15E);
+static_assert!(mem::offset_of!(NdsHeader, debug_rom_offset) == 0x160);
+static_assert!(mem::offset_of!(NdsHeader, reserved5) == 0x170);
+
+/// DSi compatibility of a ROM, from the unit code.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DsiCompat {
+    /// `0x00`, NDS only.
+    None,
+    /// `0x02`, NDS with DSi enhancements.
+    Enhanced,
+    /// `0x03`, DSi only.
+    Exclusive,
+}
+
+/// Console region lock of a ROM, from the [`nds_region`] flags.
+///
+/// [`nds_region`]: NdsHeader#structfield.nds_region
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NdsRegionLock {
+    /// `0x00`, not region locked.
+    Normal,
+    /// `0x40`, Korea.
+    Korea,
+    /// `0x80`, China (iQue).
+    China,
+}
+
+/// Launcher boot behaviour, derived from the [`autostart`] bit.
+///
+/// All fields are driven by the same bit; they are split out for boot
+/// simulators that model each step of the launcher separately.
+///
+/// [`autostart`]: NdsHeader#structfield.autostart
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BootFlow {
+    /// Skip the "Press Button" prompt after the Health and Safety screen.
+    pub skip_health_safety: bool,
+    /// Skip the boot menu, even if the firmware is set to Manual mode.
+    pub skip_boot_menu: bool,
+    /// Skip the boot menu even with Start pressed.
+    ///
+    /// Otherwise, holding Start enters the boot menu if the firmware is set
+    /// to Auto mode.
+    pub ignore_start_button: bool,
+}
+
+/// Secure area boot configuration, from the header.
+///
+/// Returned by [`NdsHeader::secure_area_config`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SecureAreaConfig {
+    /// The secure area checksum, see [`secure_area_crc16`].
+    ///
+    /// [`secure_area_crc16`]: NdsHeader#structfield.secure_area_crc16
+    pub crc16: u16,
+    /// The secure area delay in milliseconds, see
+    /// [`NdsHeader::secure_area_delay_ms`].
+    pub delay_ms: f64,
+    /// Whether the secure area is disabled, see
+    /// [`NdsHeader::is_secure_area_disabled`].
+    pub disabled: bool,
+}
+
+/// The decrypted secure area disable field, from the header.
+///
+/// Returned by [`NdsHeader::secure_area_disable_marker`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SecureAreaDisable {
+    /// The field decrypted with KEY1 level 2.
+    pub decrypted: [u8; 8],
+    /// Whether the decrypted field is the `"NmMdOnly"` marker, which disables
+    /// loading of the secure area during boot.
+    pub disabled: bool,
+}
+
+/// The modes and capabilities of a ROM on the DSi (TWL).
+///
+/// Returned by [`NdsHeader::twl_capabilities`].
+///
+/// # Sources
+///
+/// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgeheader>
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwlCapabilities {
+    /// Runs in DS (NTR) mode, from the unit code.
+    pub ds_mode: bool,
+    /// Runs in DSi (TWL) mode, from the unit code.
+    pub dsi_mode: bool,
+    /// Uses the touchscreen/sound controller in DSi mode, from bit 2 of the
+    /// DSi flags.
+    pub dsi_tsc_mode: bool,
+    /// A DSi system title, such as the system menu (launcher) or system
+    /// settings.
+    ///
+    /// System titles have game codes starting with `H`, this is a heuristic.
+    pub system_title: bool,
+}
+
+impl TwlCapabilities {
+    /// Returns `true` if the ROM runs in both DS and DSi mode.
+    pub fn is_hybrid(&self) -> bool {
+        self.ds_mode && self.dsi_mode
+    }
+}
 
 impl NdsHeader {
     /// The size of a header in bytes.
     pub const SIZE: usize = mem::size_of::<Self>();
 
+    /// The minimum size of a header in bytes.
+    ///
+    /// Small homebrew ROMs may be truncated after the header checksum, with the
+    /// remaining fields being implicitly zero.
+    pub const MIN_SIZE: usize = 0x160;
+
     pub(crate) fn read(rom: &[u8]) -> NdsHeader {
-        #[inline(always)]
-        #[cfg(target_endian = "little")]
-        unsafe fn read(bytes: &[u8]) -> NdsHeader {
-            mem::transmute_copy(&*(bytes.as_ptr() as *const [u8; NdsHeader::SIZE]))
-        }
+        let bytes = rom[0..NdsHeader::SIZE].try_into().unwrap();
 
-        #[inline(always)]
-        #[cfg(target_endian = "big")]
-        unsafe fn read(bytes: &[u8]) -> NdsHeader {
-            panic!("big-endian targets are not yet supported")
+        NdsHeader::from_bytes_const(bytes)
+    }
+
+    /// Parses a header from the start of `bytes`, such as the first
+    /// [`NdsHeader::SIZE`] bytes of a ROM, without loading the whole ROM.
+    ///
+    /// Trailing data is ignored.
+    pub fn parse(bytes: &[u8]) -> Result<NdsHeader, HeaderError> {
+        match bytes.get(..NdsHeader::SIZE) {
+            Some(bytes) => Ok(NdsHeader::read(bytes)),
+            None => Err(HeaderError::TooSmall { size: bytes.len() }),
         }
+    }
+
+    /// Reads a header from bytes.
+    ///
+    /// This can be used in const contexts, eg. with a ROM embedded by
+    /// [`include_bytes!`].
+    pub const fn from_bytes_const(bytes: &[u8; NdsHeader::SIZE]) -> NdsHeader {
+        // SAFETY: `NdsHeader` is `repr(C)` with no padding, and any bit pattern
+        // is valid for its fields.
+        let header: NdsHeader = unsafe { mem::transmute(*bytes) };
+
+        header.swap_le()
+    }
+
+    /// Returns the header as bytes, as stored in ROM.
+    ///
+    /// Numeric fields are written little-endian, regardless of the host
+    /// endianness.
+    pub const fn to_bytes(&self) -> [u8; NdsHeader::SIZE] {
+        let header = self.swap_le();
 
-        let bytes = &rom[0..NdsHeader::SIZE];
+        // SAFETY: `NdsHeader` is `repr(C)` with no padding.
+        unsafe { mem::transmute(header) }
+    }
+
+    /// Converts the numeric fields between little-endian and host order.
+    ///
+    /// This is a no-op on little-endian targets.
+    const fn swap_le(&self) -> NdsHeader {
+        let mut h = *self;
+
+        h.arm9_rom_offset = u32::from_le(h.arm9_rom_offset);
+        h.arm9_entry_address = u32::from_le(h.arm9_entry_address);
+        h.arm9_ram_address = u32::from_le(h.arm9_ram_address);
+        h.arm9_size = u32::from_le(h.arm9_size);
+        h.arm7_rom_offset = u32::from_le(h.arm7_rom_offset);
+        h.arm7_entry_address = u32::from_le(h.arm7_entry_address);
+        h.arm7_ram_address = u32::from_le(h.arm7_ram_address);
+        h.arm7_size = u32::from_le(h.arm7_size);
+        h.fnt_offset = u32::from_le(h.fnt_offset);
+        h.fnt_size = u32::from_le(h.fnt_size);
+        h.fat_offset = u32::from_le(h.fat_offset);
+        h.fat_size = u32::from_le(h.fat_size);
+        h.arm9_overlay_offset = u32::from_le(h.arm9_overlay_offset);
+        h.arm9_overlay_size = u32::from_le(h.arm9_overlay_size);
+        h.arm7_overlay_offset = u32::from_le(h.arm7_overlay_offset);
+        h.arm7_overlay_size = u32::from_le(h.arm7_overlay_size);
+        h.normal_command_settings = u32::from_le(h.normal_command_settings);
+        h.key1_command_settings = u32::from_le(h.key1_command_settings);
+        h.banner_offset = u32::from_le(h.banner_offset);
+        h.secure_area_crc16 = u16::from_le(h.secure_area_crc16);
+        h.secure_area_delay = u16::from_le(h.secure_area_delay);
+        h.arm9_autoload = u32::from_le(h.arm9_autoload);
+        h.arm7_autoload = u32::from_le(h.arm7_autoload);
+        h.secure_area_disable = u64::from_le(h.secure_area_disable);
+        h.rom_size = u32::from_le(h.rom_size);
+        h.header_size = u32::from_le(h.header_size);
+        h.unknown1 = u32::from_le(h.unknown1);
+        h.nand_rom_end = u16::from_le(h.nand_rom_end);
+        h.nand_rw_start = u16::from_le(h.nand_rw_start);
+        h.nintendo_logo_crc16 = u16::from_le(h.nintendo_logo_crc16);
+        h.header_crc16 = u16::from_le(h.header_crc16);
+        h.debug_rom_offset = u32::from_le(h.debug_rom_offset);
+        h.debug_size = u32::from_le(h.debug_size);
+        h.debug_ram_address = u32::from_le(h.debug_ram_address);
+        h.reserved4 = u32::from_le(h.reserved4);
 
-        // SAFETY: `bytes` is valid for reads of `NdsHeader::SIZE` bytes.
-        unsafe { read(bytes) }
+        h
     }
 
     /// Returns `true` if the ROM is a DSi ROM.
+    ///
+    /// This includes both DSi enhanced and DSi exclusive ROMs, see
+    /// [`dsi_compatibility`].
+    ///
+    /// [`dsi_compatibility`]: NdsHeader::dsi_compatibility
     pub fn is_dsi(&self) -> bool {
         self.unit_code & 0x02 != 0
     }
 
+    /// Returns the DSi compatibility of the ROM.
+    pub fn dsi_compatibility(&self) -> DsiCompat {
+        match self.unit_code & 0x03 {
+            0x02 => DsiCompat::Enhanced,
+            0x03 => DsiCompat::Exclusive,
+            _ => DsiCompat::None,
+        }
+    }
+
+    /// Returns the modes and capabilities of the ROM on the DSi, combining the
+    /// unit code, DSi flags and game code.
+    pub fn twl_capabilities(&self) -> TwlCapabilities {
+        let dsi_mode = self.is_dsi();
+
+        TwlCapabilities {
+            ds_mode: self.dsi_compatibility() != DsiCompat::Exclusive,
+            dsi_mode,
+            dsi_tsc_mode: dsi_mode && self.dsi_flags & 0x04 != 0,
+            system_title: dsi_mode && self.game_code.buf[0] == b'H',
+        }
+    }
+
+    /// Returns `true` if the autostart bit is set, skipping the "Press Button"
+    /// prompt after the Health and Safety screen.
+    ///
+    /// The launcher then also skips the boot menu, even if the firmware is set
+    /// to Manual mode and even with Start pressed.
+    ///
+    /// # Sources
+    ///
+    /// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgeheader>
+    pub fn is_autostart(&self) -> bool {
+        self.autostart & 0x04 != 0
+    }
+
+    /// Returns the launcher boot behaviour, derived from [`is_autostart`].
+    ///
+    /// [`is_autostart`]: NdsHeader::is_autostart
+    pub fn boot_flow(&self) -> BootFlow {
+        let autostart = self.is_autostart();
+
+        BootFlow {
+            skip_health_safety: autostart,
+            skip_boot_menu: autostart,
+            ignore_start_button: autostart,
+        }
+    }
+
+    /// Returns the console region the ROM is locked to, or `None` if the ROM
+    /// is not region locked.
+    ///
+    /// # Sources
+    ///
+    /// - [GBATEK](https://problemkaputt.de/gbatek.htm#dscartridgeheader)
+    pub fn region_locked_to(&self) -> Option<NdsRegionLock> {
+        if self.nds_region & 0x80 != 0 {
+            Some(NdsRegionLock::China)
+        } else if self.nds_region & 0x40 != 0 {
+            Some(NdsRegionLock::Korea)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the ROM boots on a console of the given region.
+    ///
+    /// Unlocked ROMs boot on every console, while Korea and China ROMs only
+    /// boot on consoles of their own region.
+    pub fn can_run_in_region(&self, region: NdsRegionLock) -> bool {
+        match self.region_locked_to() {
+            Some(locked) => locked == region,
+            None => true,
+        }
+    }
+
     /// Returns `true` if the ROM has Infrared (IR).
     pub fn has_ir(&self) -> bool {
         self.game_code.buf[0] == b'I'
@@ -260,17 +558,51 @@ impl NdsHeader {
         self.arm9_rom_offset < 0x8000 && self.arm9_rom_offset >= 0x4000
     }
 
+    /// Returns `true` if the ROM appears to be a multiboot (DS Download Play)
+    /// child.
+    ///
+    /// Download Play children are transferred wirelessly rather than read from
+    /// a cartridge, so they have no secure area; the ARM9 binary follows the
+    /// header in the normal area and is loaded into main RAM.
+    ///
+    /// # Notes
+    ///
+    /// This is a heuristic, homebrew ROMs are excluded by their `####` game code.
+    pub fn is_multiboot(&self) -> bool {
+        const MAIN_RAM: std::ops::Range<u32> = 0x2000000..0x2400000;
+        const MAX_SIZE: u32 = 0x3BFE00;
+
+        let arm9_end = self.arm9_ram_address.saturating_add(self.arm9_size);
+
+        !self.has_secure_area()
+            && self.game_code.buf != *b"####"
+            && self.arm9_rom_offset >= NdsHeader::SIZE as u32
+            && MAIN_RAM.contains(&self.arm9_ram_address)
+            && arm9_end <= MAIN_RAM.end
+            && self.arm9_size.saturating_add(self.arm7_size) <= MAX_SIZE
+    }
+
     /// Returns the game code as a `u32`.
     pub fn game_code(&self) -> u32 {
         u32::from_le_bytes(self.game_code.buf)
     }
 
+    /// Returns the maker code as a `u16`.
+    pub fn maker_code_u16(&self) -> u16 {
+        u16::from_le_bytes(self.maker_code.buf)
+    }
+
     /// Returns the region as determined from the game code.
     pub fn region(&self) -> Option<&'static str> {
         let region = self.game_code[3];
         REGIONS.get(&region).map(|&s| s)
     }
 
+    /// Returns the market region as determined from the game code.
+    pub fn market_region(&self) -> Option<Region> {
+        Region::from_code(self.game_code[3])
+    }
+
     /// Returns the manufacturer as determined from the maker code.
     pub fn maker(&self) -> Option<&'static str> {
         match self.maker_code.to_str() {
@@ -279,9 +611,274 @@ impl NdsHeader {
         }
     }
 
+    /// Returns the number of bytes used by the ROM, from [`rom_size`].
+    ///
+    /// # ROM sizes
+    ///
+    /// There are three distinct sizes of a ROM:
+    ///
+    /// - The used size, returned by this method, is the size of the ROM data
+    ///   written by the build tools. Any bytes after this are padding.
+    /// - The padded size, `rom.len()` of an [`NdsRom`], is the size of the
+    ///   loaded data, which is usually padded to a power of two.
+    /// - The device capacity, returned by [`device_capacity_bytes`], is the
+    ///   size of the cartridge chip, which is at least the used size.
+    ///
+    /// [`rom_size`]: #structfield.rom_size
+    /// [`NdsRom`]: crate::nds::NdsRom
+    /// [`device_capacity_bytes`]: NdsHeader::device_capacity_bytes
+    #[inline]
+    pub fn used_rom_size(&self) -> usize {
+        self.rom_size as usize
+    }
+
+    /// Returns the ROM address range of the ARM9 binary.
+    #[inline]
+    pub fn arm9_rom_range(&self) -> Range<usize> {
+        rom_range(self.arm9_rom_offset, self.arm9_size)
+    }
+
+    /// Returns the ROM address range of the ARM7 binary.
+    #[inline]
+    pub fn arm7_rom_range(&self) -> Range<usize> {
+        rom_range(self.arm7_rom_offset, self.arm7_size)
+    }
+
+    /// Returns the ROM address range of the file name table (FNT).
+    #[inline]
+    pub fn fnt_range(&self) -> Range<usize> {
+        rom_range(self.fnt_offset, self.fnt_size)
+    }
+
+    /// Returns the ROM address range of the file allocation table (FAT).
+    #[inline]
+    pub fn fat_range(&self) -> Range<usize> {
+        rom_range(self.fat_offset, self.fat_size)
+    }
+
+    /// Returns the ROM address range of the ARM9 overlay table.
+    #[inline]
+    pub fn arm9_overlay_range(&self) -> Range<usize> {
+        rom_range(self.arm9_overlay_offset, self.arm9_overlay_size)
+    }
+
+    /// Returns the ROM address range of the ARM7 overlay table.
+    #[inline]
+    pub fn arm7_overlay_range(&self) -> Range<usize> {
+        rom_range(self.arm7_overlay_offset, self.arm7_overlay_size)
+    }
+
+    /// Returns the ROM address range of the banner, or `None` if there is no
+    /// banner.
+    ///
+    /// The range covers the full [`NdsBanner::SIZE`], regardless of the banner
+    /// version, see [`NdsRom::banner_range`] for the range of a loaded banner.
+    ///
+    /// [`NdsRom::banner_range`]: crate::nds::NdsRom::banner_range
+    pub fn banner_range(&self) -> Option<Range<usize>> {
+        (self.banner_offset != 0).then(|| rom_range(self.banner_offset, NdsBanner::SIZE as u32))
+    }
+
     /// Returns the device capacity in bytes.
+    ///
+    /// The size saturates at `usize::MAX` if the capacity is too large to
+    /// represent, eg. for a corrupt header, see
+    /// [`checked_device_capacity_bytes`]. See [`used_rom_size`] for how this
+    /// differs from the other ROM sizes.
+    ///
+    /// [`checked_device_capacity_bytes`]: NdsHeader::checked_device_capacity_bytes
+    /// [`used_rom_size`]: NdsHeader::used_rom_size
     pub fn device_capacity_bytes(&self) -> usize {
-        (128 * 1024) << self.device_capacity
+        self.checked_device_capacity_bytes()
+            .and_then(|size| usize::try_from(size).ok())
+            .unwrap_or(usize::MAX)
+    }
+
+    /// Returns the device capacity in bytes, or `None` if the capacity is too
+    /// large to represent, eg. for a corrupt header.
+    pub fn checked_device_capacity_bytes(&self) -> Option<u64> {
+        capacity_bytes(self.device_capacity)
+    }
+
+    /// Returns the `(offset, name, size)` of every header field, in offset
+    /// order, including the reserved fields.
+    ///
+    /// The names are those of the struct fields, and the fields cover the
+    /// whole header without gaps.
+    pub fn field_map() -> &'static [(usize, &'static str, usize)] {
+        FIELD_MAP
+    }
+
+    /// Returns the reserved bytes at `0x015`.
+    pub fn reserved1(&self) -> &[u8; 7] {
+        &self.reserved1
+    }
+
+    /// Returns the unknown value at `0x088`.
+    pub fn unknown1(&self) -> u32 {
+        self.unknown1
+    }
+
+    /// Returns the reserved bytes at `0x08C`.
+    pub fn reserved2(&self) -> &[u8; 8] {
+        &self.reserved2
+    }
+
+    /// Returns the reserved bytes at `0x098`.
+    pub fn reserved3(&self) -> &[u8; 40] {
+        &self.reserved3
+    }
+
+    /// Returns the reserved value at `0x16C`.
+    pub fn reserved4(&self) -> u32 {
+        self.reserved4
+    }
+
+    /// Returns the reserved bytes at `0x170`.
+    pub fn reserved5(&self) -> &[u8; 144] {
+        &self.reserved5
+    }
+
+    /// Returns the runs of at least 4 printable ASCII characters in the
+    /// reserved and unknown fields, with their header offsets.
+    ///
+    /// The fields are usually zero filled, but some prototype and debug ROMs
+    /// store build strings in them.
+    pub fn reserved_strings(&self) -> Vec<(usize, String)> {
+        const MIN_LEN: usize = 4;
+
+        let bytes = self.to_bytes();
+        let reserved = FIELD_MAP
+            .iter()
+            .filter(|(_, name, _)| name.starts_with("reserved") || name.starts_with("unknown"));
+
+        let mut strings = Vec::new();
+        for &(offset, _, size) in reserved {
+            let mut start = offset;
+            for run in bytes[offset..(offset + size)].split(|&b| !(b' '..=b'~').contains(&b)) {
+                if run.len() >= MIN_LEN {
+                    // Printable ASCII is valid UTF-8.
+                    strings.push((start, String::from_utf8_lossy(run).into_owned()));
+                }
+                start += run.len() + 1;
+            }
+        }
+        strings
+    }
+
+    /// Reads the byte at `offset` in the header, or `None` if it is out of
+    /// bounds.
+    ///
+    /// Offsets are as in the ROM, see [`field_map`](NdsHeader::field_map).
+    pub fn get_u8(&self, offset: usize) -> Option<u8> {
+        self.get_bytes(offset).map(u8::from_le_bytes)
+    }
+
+    /// Reads the little-endian `u16` at `offset` in the header, or `None` if it
+    /// is out of bounds.
+    ///
+    /// Offsets are as in the ROM, see [`field_map`](NdsHeader::field_map).
+    pub fn get_u16(&self, offset: usize) -> Option<u16> {
+        self.get_bytes(offset).map(u16::from_le_bytes)
+    }
+
+    /// Reads the little-endian `u32` at `offset` in the header, or `None` if it
+    /// is out of bounds.
+    ///
+    /// Offsets are as in the ROM, see [`field_map`](NdsHeader::field_map).
+    pub fn get_u32(&self, offset: usize) -> Option<u32> {
+        self.get_bytes(offset).map(u32::from_le_bytes)
+    }
+
+    fn get_bytes<const N: usize>(&self, offset: usize) -> Option<[u8; N]> {
+        let end = offset.checked_add(N)?;
+        self.to_bytes().get(offset..end)?.try_into().ok()
+    }
+
+    /// Returns the secure area delay in milliseconds.
+    pub fn secure_area_delay_ms(&self) -> f64 {
+        // The delay is in 131kHz units.
+        self.secure_area_delay as f64 / 131.0
+    }
+
+    /// Returns `true` if the [`secure_area_disable`] field holds the encrypted
+    /// `"NmMdOnly"` marker, which disables loading of the secure area.
+    ///
+    /// The marker is encrypted with KEY1 [level 2](Key1::init2).
+    ///
+    /// # Sources
+    ///
+    /// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgesecurearea>
+    ///
+    /// [`secure_area_disable`]: #structfield.secure_area_disable
+    pub fn is_secure_area_disabled(&self) -> bool {
+        self.secure_area_disable_marker()
+            .is_some_and(|marker| marker.disabled)
+    }
+
+    /// Decrypts the [`secure_area_disable`] field, or returns `None` if it is
+    /// zero (not present).
+    ///
+    /// See [`is_secure_area_disabled`](NdsHeader::is_secure_area_disabled).
+    ///
+    /// [`secure_area_disable`]: #structfield.secure_area_disable
+    pub fn secure_area_disable_marker(&self) -> Option<SecureAreaDisable> {
+        if self.secure_area_disable == 0 {
+            return None;
+        }
+
+        let mut decrypted = self.secure_area_disable.to_le_bytes();
+        Key1::init2(self.game_code()).decrypt_block(&mut decrypted);
+        Some(SecureAreaDisable {
+            decrypted,
+            disabled: decrypted == NM_MD_ONLY,
+        })
+    }
+
+    /// Returns the secure area checksum, delay and disable state together, as
+    /// used to configure the boot sequence.
+    pub fn secure_area_config(&self) -> SecureAreaConfig {
+        SecureAreaConfig {
+            crc16: self.secure_area_crc16,
+            delay_ms: self.secure_area_delay_ms(),
+            disabled: self.is_secure_area_disabled(),
+        }
+    }
+
+    /// Returns the decoded port `0x40001A4` settings for normal commands.
+    pub fn normal_command_settings(&self) -> CommandSettings {
+        CommandSettings::from_bits(self.normal_command_settings)
+    }
+
+    /// Returns the decoded port `0x40001A4` settings for KEY1 commands.
+    pub fn key1_command_settings(&self) -> CommandSettings {
+        CommandSettings::from_bits(self.key1_command_settings)
+    }
+
+    /// Sets the smallest device capacity that covers `rom_size` bytes, and
+    /// updates the total ROM size.
+    ///
+    /// The header size is set to the conventional `0x4000` if it is unset, and
+    /// the total ROM size saturates at `u32::MAX`, the largest size the header
+    /// can store.
+    ///
+    /// # Notes
+    ///
+    /// The header checksum is not updated.
+    pub fn fit_device_capacity(&mut self, rom_size: usize) {
+        self.device_capacity = fit_capacity(rom_size as u64);
+        self.rom_size = u32::try_from(rom_size).unwrap_or(u32::MAX);
+        if self.header_size == 0 {
+            self.header_size = 0x4000;
+        }
+    }
+
+    /// Returns `true` if the Nintendo logo is genuine.
+    ///
+    /// This compares the logo bitmap itself, as verified by the firmware,
+    /// rather than the checksum verified by the BIOS.
+    pub fn logo_is_genuine(&self) -> bool {
+        self.nintendo_logo == NINTENDO_LOGO
     }
 
     /// Computes the Nintendo logo checksum.
@@ -296,4 +893,256 @@ impl NdsHeader {
         let bytes = unsafe { std::slice::from_raw_parts(ptr, 0x15E) };
         crc::crc16(bytes)
     }
+
+    /// Updates [`header_crc16`] to match the header contents.
+    ///
+    /// Call this after editing the header, to produce a valid header.
+    ///
+    /// [`header_crc16`]: #structfield.header_crc16
+    pub fn fix_crc(&mut self) {
+        self.header_crc16 = self.compute_header_crc16();
+    }
+
+    /// Sets [`game_title`], padding it with zeros.
+    ///
+    /// The title must be printable ASCII of at most 12 characters. Retail
+    /// titles are uppercase, a lowercase title is accepted with a warning.
+    ///
+    /// # Notes
+    ///
+    /// The header checksum is not updated, see [`fix_crc`].
+    ///
+    /// [`game_title`]: #structfield.game_title
+    /// [`fix_crc`]: NdsHeader::fix_crc
+    pub fn set_game_title(&mut self, title: &str) -> Result<(), HeaderEditError> {
+        validate_field(title)?;
+        if title.len() > self.game_title.buf.len() {
+            return Err(HeaderEditError::TooLong {
+                len: title.len(),
+                capacity: self.game_title.buf.len(),
+            });
+        }
+        if title.bytes().any(|c| c.is_ascii_lowercase()) {
+            log::warn!("game title is not uppercase: {:?}", title);
+        }
+
+        self.game_title = Ascii::default();
+        self.game_title.buf[..title.len()].copy_from_slice(title.as_bytes());
+        Ok(())
+    }
+
+    /// Sets [`game_code`], converting it to uppercase.
+    ///
+    /// The code must be exactly 4 printable ASCII characters.
+    ///
+    /// # Notes
+    ///
+    /// The header checksum is not updated, see [`fix_crc`]. The game code
+    /// also keys the secure area encryption, an encrypted secure area must
+    /// be re-encrypted for the new code.
+    ///
+    /// [`game_code`]: #structfield.game_code
+    /// [`fix_crc`]: NdsHeader::fix_crc
+    pub fn set_game_code(&mut self, code: &str) -> Result<(), HeaderEditError> {
+        validate_field(code)?;
+        if code.len() != self.game_code.buf.len() {
+            return Err(HeaderEditError::WrongLength {
+                len: code.len(),
+                expected: self.game_code.buf.len(),
+            });
+        }
+
+        self.game_code.buf.copy_from_slice(code.as_bytes());
+        self.game_code.buf.make_ascii_uppercase();
+        Ok(())
+    }
+}
+
+/// Checks that a header string field contains only printable ASCII.
+fn validate_field(s: &str) -> Result<(), HeaderEditError> {
+    match s.bytes().position(|c| !(0x20..0x7F).contains(&c)) {
+        Some(index) => Err(HeaderEditError::InvalidChar { index }),
+        None => Ok(()),
+    }
+}
+
+/// Defines setters for numeric header fields.
+///
+/// Fields are stored in host order, and converted to little-endian by
+/// [`NdsHeader::to_bytes`].
+macro_rules! setters {
+    ($($set:ident => $field:ident: $ty:ty,)*) => {
+        impl NdsHeader {
+            $(
+                #[doc = concat!("Sets [`", stringify!($field), "`](#structfield.", stringify!($field), ").")]
+                #[inline]
+                pub fn $set(&mut self, value: $ty) {
+                    self.$field = value;
+                }
+            )*
+        }
+    };
+}
+
+setters! {
+    set_arm9_rom_offset => arm9_rom_offset: u32,
+    set_arm9_entry_address => arm9_entry_address: u32,
+    set_arm9_ram_address => arm9_ram_address: u32,
+    set_arm9_size => arm9_size: u32,
+    set_arm7_rom_offset => arm7_rom_offset: u32,
+    set_arm7_entry_address => arm7_entry_address: u32,
+    set_arm7_ram_address => arm7_ram_address: u32,
+    set_arm7_size => arm7_size: u32,
+    set_fnt_offset => fnt_offset: u32,
+    set_fnt_size => fnt_size: u32,
+    set_fat_offset => fat_offset: u32,
+    set_fat_size => fat_size: u32,
+    set_arm9_overlay_offset => arm9_overlay_offset: u32,
+    set_arm9_overlay_size => arm9_overlay_size: u32,
+    set_arm7_overlay_offset => arm7_overlay_offset: u32,
+    set_arm7_overlay_size => arm7_overlay_size: u32,
+    set_normal_command_settings => normal_command_settings: u32,
+    set_key1_command_settings => key1_command_settings: u32,
+    set_banner_offset => banner_offset: u32,
+    set_secure_area_crc16 => secure_area_crc16: u16,
+    set_secure_area_delay => secure_area_delay: u16,
+    set_arm9_autoload => arm9_autoload: u32,
+    set_arm7_autoload => arm7_autoload: u32,
+    set_secure_area_disable => secure_area_disable: u64,
+    set_rom_size => rom_size: u32,
+    set_header_size => header_size: u32,
+    set_nand_rom_end => nand_rom_end: u16,
+    set_nand_rw_start => nand_rw_start: u16,
+    set_nintendo_logo_crc16 => nintendo_logo_crc16: u16,
+    set_header_crc16 => header_crc16: u16,
+    set_debug_rom_offset => debug_rom_offset: u32,
+    set_debug_size => debug_size: u32,
+    set_debug_ram_address => debug_ram_address: u32,
+}
+
+/// Defines typed getters for the header fields holding ROM offsets and RAM
+/// addresses.
+macro_rules! typed_getters {
+    ($($field:ident: $ty:ident,)*) => {
+        impl NdsHeader {
+            $(
+                #[doc = concat!("Returns [`", stringify!($field), "`](#structfield.", stringify!($field), ") as a [`", stringify!($ty), "`].")]
+                #[inline]
+                pub fn $field(&self) -> $ty {
+                    $ty(self.$field)
+                }
+            )*
+        }
+    };
+}
+
+typed_getters! {
+    arm9_rom_offset: RomOffset,
+    arm9_entry_address: RamAddress,
+    arm9_ram_address: RamAddress,
+    arm7_rom_offset: RomOffset,
+    arm7_entry_address: RamAddress,
+    arm7_ram_address: RamAddress,
+    fnt_offset: RomOffset,
+    fat_offset: RomOffset,
+    arm9_overlay_offset: RomOffset,
+    arm7_overlay_offset: RomOffset,
+    banner_offset: RomOffset,
+    arm9_autoload: RamAddress,
+    arm7_autoload: RamAddress,
+    debug_rom_offset: RomOffset,
+    debug_ram_address: RamAddress,
+}
+
+/// Lists the `(offset, name, size)` of header fields, in order.
+macro_rules! field_map {
+    ($($field:ident,)*) => {
+        &[$(
+            (
+                mem::offset_of!(NdsHeader, $field),
+                stringify!($field),
+                field_size(|header: &NdsHeader| &header.$field),
+            ),
+        )*]
+    };
+}
+
+/// Returns the size of the field returned by `field`.
+const fn field_size<T>(_field: fn(&NdsHeader) -> &T) -> usize {
+    mem::size_of::<T>()
+}
+
+static FIELD_MAP: &[(usize, &str, usize)] = field_map! {
+    game_title,
+    game_code,
+    maker_code,
+    unit_code,
+    device_type,
+    device_capacity,
+    reserved1,
+    dsi_flags,
+    nds_region,
+    rom_version,
+    autostart,
+    arm9_rom_offset,
+    arm9_entry_address,
+    arm9_ram_address,
+    arm9_size,
+    arm7_rom_offset,
+    arm7_entry_address,
+    arm7_ram_address,
+    arm7_size,
+    fnt_offset,
+    fnt_size,
+    fat_offset,
+    fat_size,
+    arm9_overlay_offset,
+    arm9_overlay_size,
+    arm7_overlay_offset,
+    arm7_overlay_size,
+    normal_command_settings,
+    key1_command_settings,
+    banner_offset,
+    secure_area_crc16,
+    secure_area_delay,
+    arm9_autoload,
+    arm7_autoload,
+    secure_area_disable,
+    rom_size,
+    header_size,
+    unknown1,
+    reserved2,
+    nand_rom_end,
+    nand_rw_start,
+    reserved3,
+    nintendo_logo,
+    nintendo_logo_crc16,
+    header_crc16,
+    debug_rom_offset,
+    debug_size,
+    debug_ram_address,
+    reserved4,
+    reserved5,
+};
+
+/// Returns the size in bytes of a device capacity, `128KB << capacity`, or
+/// `None` if it overflows.
+fn capacity_bytes(capacity: u8) -> Option<u64> {
+    // `128KB = 1 << 17`.
+    1u64.checked_shl(17 + capacity as u32)
+}
+
+/// Returns the smallest device capacity that covers `size` bytes.
+pub(crate) fn fit_capacity(size: u64) -> u8 {
+    let mut capacity = 0;
+    while capacity_bytes(capacity).is_some_and(|bytes| bytes < size) {
+        capacity += 1;
+    }
+    capacity
+}
+
+/// Returns the range `offset..(offset + size)`, saturating at `usize::MAX`.
+fn rom_range(offset: u32, size: u32) -> Range<usize> {
+    let start = offset as usize;
+    start..start.saturating_add(size as usize)
 }
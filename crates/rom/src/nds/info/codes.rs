@@ -344,3 +344,19 @@ pub static MAKERS: phf::Map<&'static str, &'static str> = phf::phf_map! {
     "GD" => "Square-Enix",
     "IH" => "Yojigen",
 };
+
+/// Returns an iterator over all known `(region code, region)` pairs, sorted by
+/// region code.
+pub fn all_regions() -> impl Iterator<Item = (u8, &'static str)> {
+    let mut regions = REGIONS.entries().map(|(&k, &v)| (k, v)).collect::<Vec<_>>();
+    regions.sort_unstable();
+    regions.into_iter()
+}
+
+/// Returns an iterator over all known `(maker code, maker)` pairs, sorted by
+/// maker code.
+pub fn all_makers() -> impl Iterator<Item = (&'static str, &'static str)> {
+    let mut makers = MAKERS.entries().map(|(&k, &v)| (k, v)).collect::<Vec<_>>();
+    makers.sort_unstable();
+    makers.into_iter()
+}
@@ -1,5 +1,9 @@
 mod codes;
+mod region;
 mod roms;
+mod strategy;
 
-pub use self::codes::{MAKERS, REGIONS};
+pub use self::codes::{all_makers, all_regions, MAKERS, REGIONS};
+pub use self::region::Region;
 pub use self::roms::{MemoryKind, RomParams, SramKind};
+pub use self::strategy::{MelonDsStrategy, SizeHeuristicStrategy, SramStrategy};
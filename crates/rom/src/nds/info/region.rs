@@ -0,0 +1,140 @@
+use crate::nds::banner::BannerLanguage;
+use crate::nds::info::REGIONS;
+
+/// A market region, from the last character of the game code.
+///
+/// Some region codes have conflicting uses, the most common is chosen, see
+/// [`all_regions`].
+///
+/// [`all_regions`]: crate::nds::all_regions
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Region {
+    /// `C`.
+    China,
+    /// `D`.
+    Germany,
+    /// `E`.
+    Usa,
+    /// `F`.
+    France,
+    /// `G`.
+    Greece,
+    /// `H`.
+    Netherlands,
+    /// `I`.
+    Italy,
+    /// `J`.
+    Japan,
+    /// `K`.
+    Korea,
+    /// `L`.
+    Canada,
+    /// `M`.
+    Sweden,
+    /// `N`.
+    Norway,
+    /// `P`.
+    Europe,
+    /// `Q`.
+    Denmark,
+    /// `R`.
+    Russia,
+    /// `S`.
+    Spain,
+    /// `U`.
+    Australia,
+    /// `V`, other European releases.
+    EuropeV,
+    /// `W`, other European releases.
+    EuropeW,
+    /// `X`, other European releases.
+    EuropeX,
+    /// `Y`, other European releases.
+    EuropeY,
+    /// `Z`, other European releases.
+    EuropeZ,
+}
+
+impl Region {
+    /// Returns the region for a region code, the last character of the game
+    /// code.
+    pub fn from_code(code: u8) -> Option<Region> {
+        Some(match code {
+            b'C' => Region::China,
+            b'D' => Region::Germany,
+            b'E' => Region::Usa,
+            b'F' => Region::France,
+            b'G' => Region::Greece,
+            b'H' => Region::Netherlands,
+            b'I' => Region::Italy,
+            b'J' => Region::Japan,
+            b'K' => Region::Korea,
+            b'L' => Region::Canada,
+            b'M' => Region::Sweden,
+            b'N' => Region::Norway,
+            b'P' => Region::Europe,
+            b'Q' => Region::Denmark,
+            b'R' => Region::Russia,
+            b'S' => Region::Spain,
+            b'U' => Region::Australia,
+            b'V' => Region::EuropeV,
+            b'W' => Region::EuropeW,
+            b'X' => Region::EuropeX,
+            b'Y' => Region::EuropeY,
+            b'Z' => Region::EuropeZ,
+            _ => return None,
+        })
+    }
+
+    /// Returns the region code.
+    pub fn code(self) -> u8 {
+        match self {
+            Region::China => b'C',
+            Region::Germany => b'D',
+            Region::Usa => b'E',
+            Region::France => b'F',
+            Region::Greece => b'G',
+            Region::Netherlands => b'H',
+            Region::Italy => b'I',
+            Region::Japan => b'J',
+            Region::Korea => b'K',
+            Region::Canada => b'L',
+            Region::Sweden => b'M',
+            Region::Norway => b'N',
+            Region::Europe => b'P',
+            Region::Denmark => b'Q',
+            Region::Russia => b'R',
+            Region::Spain => b'S',
+            Region::Australia => b'U',
+            Region::EuropeV => b'V',
+            Region::EuropeW => b'W',
+            Region::EuropeX => b'X',
+            Region::EuropeY => b'Y',
+            Region::EuropeZ => b'Z',
+        }
+    }
+
+    /// Returns the short name of the region, eg. `"USA"`.
+    pub fn name(self) -> &'static str {
+        REGIONS[&self.code()]
+    }
+
+    /// Returns the banner language a title would naturally be displayed in for
+    /// the region.
+    ///
+    /// Regions without a banner title in their own language use English.
+    pub fn preferred_language(self) -> BannerLanguage {
+        match self {
+            Region::China => BannerLanguage::Chinese,
+            Region::Germany => BannerLanguage::German,
+            Region::France => BannerLanguage::French,
+            Region::Italy => BannerLanguage::Italian,
+            Region::Japan => BannerLanguage::Japanese,
+            Region::Korea => BannerLanguage::Korean,
+            Region::Spain => BannerLanguage::Spanish,
+            _ => BannerLanguage::English,
+        }
+    }
+}
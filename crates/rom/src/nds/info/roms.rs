@@ -1,8 +1,19 @@
 //! A list of ROMs along with their size and SRAM kind.
 
+use std::cmp::Ordering;
 use std::fmt;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+use common::util::FileSize;
+
+use crate::nds::info::{MelonDsStrategy, SramStrategy};
+use crate::nds::NdsHeader;
+
+/// The kind and size of SRAM.
+///
+/// Ordered by [`size`](SramKind::size).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum SramKind {
     /// No SRAM.
     None = 0,
@@ -28,7 +39,10 @@ pub enum SramKind {
     Nand64MB = 10,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// The kind of SRAM memory.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum MemoryKind {
     /// No SRAM.
     None,
@@ -43,7 +57,8 @@ pub enum MemoryKind {
 }
 
 /// A ROM with known size and SRAM.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RomParams {
     /// The size of the ROM in bytes.
     pub rom_size: u32,
@@ -56,9 +71,138 @@ impl RomParams {
     pub fn get(game_code: u32) -> Option<&'static RomParams> {
         ROMS.get(&game_code)
     }
+
+    /// Returns parameters detected from a ROM, for ROMs not in the database.
+    ///
+    /// The SRAM kind cannot be detected, so EEPROM 64KB is assumed (same
+    /// behaviour as melonDS), except for homebrew which has no SRAM, see
+    /// [`MelonDsStrategy`].
+    pub fn detect(header: &NdsHeader, rom_size: usize) -> RomParams {
+        RomParams::detect_with(header, rom_size, &MelonDsStrategy)
+    }
+
+    /// Returns parameters detected from a ROM, for ROMs not in the database,
+    /// guessing the SRAM kind with `strategy`.
+    pub fn detect_with(
+        header: &NdsHeader,
+        rom_size: usize,
+        strategy: &dyn SramStrategy,
+    ) -> RomParams {
+        RomParams {
+            rom_size: rom_size as u32,
+            sram_kind: strategy.guess(header, rom_size),
+        }
+    }
+
+    /// Returns the kind of SRAM memory.
+    #[inline]
+    pub fn memory_kind(&self) -> MemoryKind {
+        self.sram_kind.memory_kind()
+    }
+
+    /// Returns the size of the SRAM in bytes.
+    #[inline]
+    pub fn sram_size(&self) -> usize {
+        self.sram_kind.size()
+    }
+}
+
+impl MemoryKind {
+    /// Returns the conventional write page size in bytes, or `None` if the
+    /// memory is not accessed over SPI.
+    ///
+    /// The page size of regular EEPROM depends on the chip size, this returns
+    /// the page size of the smallest chip, see [`SramKind::page_size`].
+    ///
+    /// # Sources
+    ///
+    /// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgebackup>
+    pub fn page_size(self) -> Option<usize> {
+        match self {
+            MemoryKind::EepromSmall => Some(16),
+            MemoryKind::EepromRegular => Some(32),
+            MemoryKind::Flash => Some(256),
+            MemoryKind::None | MemoryKind::Nand => None,
+        }
+    }
+
+    /// Returns the conventional number of address bytes in SPI commands, or
+    /// `None` if the memory is not accessed over SPI.
+    ///
+    /// Small EEPROM uses bit3 of the command byte as address bit8. 128KB
+    /// EEPROM uses 3 address bytes, see [`SramKind::address_bytes`].
+    ///
+    /// # Sources
+    ///
+    /// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgebackup>
+    pub fn address_bytes(self) -> Option<usize> {
+        match self {
+            MemoryKind::EepromSmall => Some(1),
+            MemoryKind::EepromRegular => Some(2),
+            MemoryKind::Flash => Some(3),
+            MemoryKind::None | MemoryKind::Nand => None,
+        }
+    }
+
+    /// Returns the value of erased bytes, which blank memory is filled with.
+    ///
+    /// EEPROM, Flash and NAND all read erased bytes as `0xFF`.
+    pub fn erased_byte(self) -> u8 {
+        match self {
+            MemoryKind::None => 0x00,
+            MemoryKind::EepromSmall
+            | MemoryKind::EepromRegular
+            | MemoryKind::Flash
+            | MemoryKind::Nand => 0xFF,
+        }
+    }
 }
 
 impl SramKind {
+    /// Returns the write page size in bytes, or `None` if the memory is not
+    /// accessed over SPI.
+    pub fn page_size(self) -> Option<usize> {
+        match self {
+            SramKind::Eeprom64KB => Some(128),
+            SramKind::Eeprom128KB => Some(256),
+            _ => self.memory_kind().page_size(),
+        }
+    }
+
+    /// Returns the number of address bytes in SPI commands, or `None` if the
+    /// memory is not accessed over SPI.
+    pub fn address_bytes(self) -> Option<usize> {
+        match self {
+            SramKind::Eeprom128KB => Some(3),
+            _ => self.memory_kind().address_bytes(),
+        }
+    }
+
+    /// Returns the typical response to the JEDEC ID command (`9Fh`, RDID), as
+    /// manufacturer, memory type and capacity bytes.
+    ///
+    /// Only Flash supports the command, the values are those of the ST
+    /// M45PE series chips used by most games. Returns `None` for EEPROM, which
+    /// reads the command as `0xFF` bytes, and for NAND, which is not accessed
+    /// over SPI.
+    ///
+    /// # Sources
+    ///
+    /// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgebackup>
+    pub fn jedec_id(self) -> Option<[u8; 3]> {
+        // ST Microelectronics, serial Flash.
+        const MANUFACTURER: u8 = 0x20;
+        const MEMORY_TYPE: u8 = 0x40;
+
+        let capacity = match self {
+            SramKind::Flash256KB => 0x12,
+            SramKind::Flash512KB => 0x13,
+            SramKind::Flash1MB => 0x14,
+            _ => return None,
+        };
+        Some([MANUFACTURER, MEMORY_TYPE, capacity])
+    }
+
     /// Returns the size of the SRAM in bytes.
     pub fn size(self) -> usize {
         match self {
@@ -76,6 +220,14 @@ impl SramKind {
         }
     }
 
+    /// Returns a blank backing buffer of [`size`] bytes, filled with the
+    /// [erased byte](MemoryKind::erased_byte) of the memory.
+    ///
+    /// [`size`]: SramKind::size
+    pub fn new_backing(self) -> Vec<u8> {
+        vec![self.memory_kind().erased_byte(); self.size()]
+    }
+
     /// Returns the kind of memory.
     pub fn memory_kind(self) -> MemoryKind {
         match self {
@@ -90,6 +242,30 @@ impl SramKind {
     }
 }
 
+impl Ord for SramKind {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.size().cmp(&other.size())
+    }
+}
+
+impl PartialOrd for SramKind {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for RomParams {
+    /// Formats the ROM size and SRAM kind, eg. `"4 MB (SRAM EEPROM 64KB)"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (SRAM {})",
+            FileSize(self.rom_size as usize),
+            self.sram_kind
+        )
+    }
+}
+
 impl fmt::Display for SramKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(match self {
@@ -0,0 +1,59 @@
+use crate::nds::info::SramKind;
+use crate::nds::NdsHeader;
+
+/// A policy for guessing the SRAM kind of ROMs not in the ROM database.
+///
+/// The SRAM kind cannot be detected from the ROM data, so emulators have to
+/// guess, see [`RomOptions::with_sram_strategy`].
+///
+/// [`RomOptions::with_sram_strategy`]: crate::nds::RomOptions::with_sram_strategy
+pub trait SramStrategy {
+    /// Guesses the SRAM kind from the header and the size of the ROM data.
+    fn guess(&self, header: &NdsHeader, rom_size: usize) -> SramKind;
+}
+
+/// Assumes EEPROM 64KB, the same behaviour as melonDS, except for homebrew
+/// which has no SRAM.
+///
+/// This is the default strategy.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MelonDsStrategy;
+
+impl SramStrategy for MelonDsStrategy {
+    fn guess(&self, header: &NdsHeader, _rom_size: usize) -> SramKind {
+        if header.is_homebrew() {
+            // No SRAM for homebrew.
+            SramKind::None
+        } else {
+            SramKind::Eeprom64KB
+        }
+    }
+}
+
+/// Guesses the SRAM kind by the size of the ROM, as larger games tend to use
+/// larger saves, except for homebrew which has no SRAM.
+///
+/// | ROM size         | SRAM kind                 |
+/// |------------------|---------------------------|
+/// | < 8MB            | [`SramKind::Eeprom8KB`]   |
+/// | 8MB..32MB        | [`SramKind::Eeprom64KB`]  |
+/// | 32MB..128MB      | [`SramKind::Flash512KB`]  |
+/// | 128MB..256MB     | [`SramKind::Flash1MB`]    |
+/// | >= 256MB         | [`SramKind::Nand64MB`]    |
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SizeHeuristicStrategy;
+
+impl SramStrategy for SizeHeuristicStrategy {
+    fn guess(&self, header: &NdsHeader, rom_size: usize) -> SramKind {
+        const MB: usize = 1024 * 1024;
+
+        match rom_size {
+            _ if header.is_homebrew() => SramKind::None,
+            size if size < 8 * MB => SramKind::Eeprom8KB,
+            size if size < 32 * MB => SramKind::Eeprom64KB,
+            size if size < 128 * MB => SramKind::Flash512KB,
+            size if size < 256 * MB => SramKind::Flash1MB,
+            _ => SramKind::Nand64MB,
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::ops::Range;
+
+use crate::nds::{NdsHeader, NdsRom, ROOT_DIR_ID};
+
+/// The kind of a [`LayoutRegion`].
+///
+/// Also returned by [`NdsRom::region_of`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RegionKind {
+    /// The ROM header.
+    Header,
+    /// The secure area, at the start of the ARM9 boot code.
+    ///
+    /// Only returned by [`NdsRom::region_of`], layout regions include the
+    /// secure area in [`RegionKind::Arm9`].
+    SecureArea,
+    /// The ARM9 boot code.
+    Arm9,
+    /// The ARM7 boot code.
+    Arm7,
+    /// The file name table (FNT).
+    Fnt,
+    /// The file allocation table (FAT).
+    Fat,
+    /// The ARM9 overlay table.
+    Arm9Overlay,
+    /// The ARM7 overlay table.
+    Arm7Overlay,
+    /// The icon/title banner.
+    Banner,
+    /// A file, with its file ID.
+    File(u16),
+    /// Space not used by any other region.
+    ///
+    /// Only returned by [`NdsRom::region_of`], see [`LayoutReport::gaps`].
+    Padding,
+}
+
+/// A region of ROM space, as described by the header or the file allocation
+/// table (FAT).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LayoutRegion {
+    /// The kind of the region.
+    pub kind: RegionKind,
+    /// The start ROM address.
+    pub start: u64,
+    /// The end ROM address (exclusive).
+    pub end: u64,
+}
+
+/// A map of how the space of a ROM is used.
+///
+/// Returned by [`NdsRom::layout_report`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LayoutReport {
+    /// The non-empty regions of the ROM, sorted by start address.
+    pub regions: Vec<LayoutRegion>,
+    /// Pairs of regions which overlap.
+    ///
+    /// Each overlapping region is paired once, with the earlier region that
+    /// extends furthest past its start.
+    pub overlaps: Vec<(LayoutRegion, LayoutRegion)>,
+    /// Unused ROM space between regions.
+    ///
+    /// Space after the last region is padding, and is not included.
+    pub gaps: Vec<Range<u64>>,
+    /// Regions which extend past the end of the ROM.
+    pub out_of_bounds: Vec<LayoutRegion>,
+}
+
+impl LayoutRegion {
+    /// Returns the ROM address range of the region.
+    #[inline]
+    pub fn range(&self) -> Range<u64> {
+        self.start..self.end
+    }
+
+    /// Returns the size of the region in bytes.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    /// Returns `true` if the region is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl LayoutReport {
+    /// Returns `true` if no regions overlap or extend past the end of the ROM.
+    ///
+    /// Gaps are not considered to be inconsistent.
+    pub fn is_consistent(&self) -> bool {
+        self.overlaps.is_empty() && self.out_of_bounds.is_empty()
+    }
+}
+
+/// The regions described by the header, in order of precedence.
+fn header_regions(rom: &NdsRom) -> [(RegionKind, Range<usize>); 8] {
+    let h = &rom.header;
+    [
+        (RegionKind::Header, 0..NdsHeader::SIZE),
+        (RegionKind::Arm9, h.arm9_rom_range()),
+        (RegionKind::Arm7, h.arm7_rom_range()),
+        (RegionKind::Fnt, h.fnt_range()),
+        (RegionKind::Fat, h.fat_range()),
+        (RegionKind::Arm9Overlay, h.arm9_overlay_range()),
+        (RegionKind::Arm7Overlay, h.arm7_overlay_range()),
+        (RegionKind::Banner, rom.banner_range().unwrap_or(0..0)),
+    ]
+}
+
+pub(crate) fn region_of(rom: &NdsRom, offset: usize) -> Option<RegionKind> {
+    if offset >= rom.rom.len() {
+        return None;
+    }
+
+    let h = &rom.header;
+    if h.has_secure_area() && (h.arm9_rom_offset as usize..0x8000).contains(&offset) {
+        return Some(RegionKind::SecureArea);
+    }
+
+    let header = header_regions(rom)
+        .into_iter()
+        .find(|(_, range)| range.contains(&offset))
+        .map(|(kind, _)| kind);
+    let file = || {
+        file_entries(rom)
+            .find(|(_, (start, end))| (*start as usize..*end as usize).contains(&offset))
+            .map(|(id, _)| RegionKind::File(id))
+    };
+
+    Some(header.or_else(file).unwrap_or(RegionKind::Padding))
+}
+
+/// Returns the FAT entries with their file IDs.
+///
+/// File IDs below [`ROOT_DIR_ID`] are files, entries past them are ignored.
+fn file_entries(rom: &NdsRom) -> impl Iterator<Item = (u16, (u32, u32))> + '_ {
+    (0..ROOT_DIR_ID).zip(rom.fat_entries())
+}
+
+pub(crate) fn layout_report(rom: &NdsRom) -> LayoutReport {
+    let header = header_regions(rom).map(|(kind, range)| LayoutRegion {
+        kind,
+        start: range.start as u64,
+        end: range.end as u64,
+    });
+    let files = file_entries(rom).map(|(id, (start, end))| LayoutRegion {
+        kind: RegionKind::File(id),
+        start: start as u64,
+        // Treat an inverted entry as empty.
+        end: (end as u64).max(start as u64),
+    });
+
+    let mut regions = header
+        .into_iter()
+        .chain(files)
+        .filter(|region| !region.is_empty())
+        .collect::<Vec<_>>();
+    regions.sort_by_key(|region| (region.start, region.end));
+
+    let mut report = LayoutReport::default();
+    let rom_size = rom.rom.len() as u64;
+
+    // The previous region extending furthest, regions are sorted so any
+    // overlap of a region is with this region.
+    let mut covering: Option<&LayoutRegion> = None;
+    let mut covered = 0;
+    for region in &regions {
+        if region.start > covered {
+            report.gaps.push(covered..region.start);
+        }
+
+        if region.end > rom_size {
+            report.out_of_bounds.push(*region);
+        }
+
+        match covering {
+            Some(other) if region.start < other.end => {
+                report.overlaps.push((*other, *region));
+                if region.end > other.end {
+                    covering = Some(region);
+                }
+            }
+            _ => covering = Some(region),
+        }
+        covered = covered.max(region.end);
+    }
+
+    report.regions = regions;
+    report
+}
@@ -1,74 +1,260 @@
+use std::borrow::Cow;
+use std::fmt;
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::ops::Range;
 use std::path::Path;
+use std::sync::{Arc, OnceLock};
 
-use common::util::{crc, FileSize};
+use byteorder::{ByteOrder, LittleEndian};
 
+use common::util::crc::{self, Crc32};
+use common::util::sha1::Sha1;
+
+mod addr;
+#[cfg(feature = "archives")]
+mod archive;
 mod banner;
+mod blz;
+mod build_info;
+mod command;
+#[cfg(feature = "hashes")]
+mod dat;
+mod dsi;
+mod error;
+mod format;
+mod fs;
 mod header;
 mod info;
+mod layout;
+mod overlay;
+mod summary;
+mod verify;
 
 pub mod encrypt;
 
-use self::encrypt::Key1;
-use self::info::{MemoryKind, RomParams, SramKind};
+use self::encrypt::SecureAreaId;
 
-pub use self::banner::NdsBanner;
-pub use self::header::NdsHeader;
+pub use self::addr::{RamAddress, RomOffset};
+pub use self::banner::{BannerLanguage, BannerVersion, NdsBanner};
+pub use self::blz::{decompress_blz, BlzFooter};
+pub use self::build_info::Arm9BuildInfo;
+pub use self::command::{CartPhase, ClockRate, CommandSettings};
+#[cfg(feature = "hashes")]
+pub use self::dat::{DatEntry, DatImage, DatVerdict};
+pub use self::dsi::DsiHeader;
+#[cfg(feature = "archives")]
+pub use self::error::ArchiveError;
+pub use self::error::{
+    BannerEditError, BannerError, FilesystemError, HeaderEditError, HeaderError, LoadDiagnostic,
+    OpenError, ReplaceError, RomError,
+};
+pub use self::format::{detect_format, RomFormat};
+pub use self::fs::{Directory, Entries, Entry, Filesystem, ROOT_DIR_ID};
+pub use self::header::{
+    BootFlow, DsiCompat, NdsHeader, NdsRegionLock, SecureAreaConfig, SecureAreaDisable,
+    TwlCapabilities, NINTENDO_LOGO,
+};
+pub use self::info::{
+    all_makers, all_regions, MelonDsStrategy, MemoryKind, Region, RomParams, SizeHeuristicStrategy,
+    SramKind, SramStrategy,
+};
+pub use self::layout::{LayoutRegion, LayoutReport, RegionKind};
+pub use self::overlay::Overlay;
+pub use self::summary::RomSummary;
+pub use self::verify::{IssueKind, Severity, VerifyIssue};
 
 /// NDS ROM.
-#[derive(Debug)]
+///
+/// # Invariants
+///
+/// The ROM data, [`rom`], is the source of truth. The [`header`] and
+/// [`dsi_header`] are copies parsed when the ROM is loaded, editing either
+/// copy does not update the ROM data, and editing the ROM data does not update
+/// the copies.
+///
+/// Use [`with_header`] to edit the header and ROM data together, or
+/// [`reparse_header`] after editing the ROM data.
+///
+/// [`rom`]: #structfield.rom
+/// [`header`]: #structfield.header
+/// [`dsi_header`]: #structfield.dsi_header
+/// [`with_header`]: NdsRom::with_header
+/// [`reparse_header`]: NdsRom::reparse_header
+#[derive(Clone, Debug)]
 pub struct NdsRom {
     /// The ROM data.
+    ///
+    /// The data is zero padded to a power of two, and at least the size of the
+    /// header, unless loaded with [`NdsRom::open_exact`].
     pub rom: Box<[u8]>,
     /// A copy of the ROM header.
+    ///
+    /// Not kept in sync with the ROM data, see [`NdsRom`].
     pub header: NdsHeader,
+    /// A copy of the DSi extended header, if it exists.
+    ///
+    /// Not kept in sync with the ROM data, see [`NdsRom`].
+    pub dsi_header: Option<DsiHeader>,
     /// A copy of the ROM banner, if it exists.
     pub banner: Option<NdsBanner>,
     /// Extra information about the ROM.
     pub params: RomParams,
     /// A generated chip ID for the ROM.
     pub chip_id: u32,
+    /// Whether the banner extends past the end of the ROM data.
+    banner_truncated: bool,
+    /// The size of the ROM data before padding.
+    data_size: usize,
+    /// Lazily computed checksums.
+    checksums: OnceLock<Checksums>,
+    /// Lazily computed hashes, indexed by [`DatImage`].
+    #[cfg(feature = "hashes")]
+    image_hashes: [OnceLock<dat::ImageHashes>; 2],
+}
+
+/// The largest cartridge ROM size in bytes, 4Gbit.
+pub const MAX_ROM_SIZE: usize = 512 * 1024 * 1024;
+
+/// Checksums computed from the ROM data.
+///
+/// Returned by [`NdsRom::checksums`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Checksums {
+    /// The header checksum, see [`NdsHeader::compute_header_crc16`].
+    pub header: u16,
+    /// The Nintendo logo checksum, see [`NdsHeader::compute_logo_crc16`].
+    pub logo: u16,
+    /// The secure area checksum, if it exists, see
+    /// [`NdsRom::compute_secure_area_crc16`].
+    pub secure_area: Option<u16>,
+}
+
+/// Hashes of a ROM file.
+///
+/// Returned by [`NdsRom::open_with_hashes`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hashes {
+    /// The CRC-32 of the file, as listed in DAT files.
+    ///
+    /// Unlike [`crc::crc32`], the final value is inverted.
+    pub crc32: u32,
+    /// The SHA-1 digest of the file.
+    pub sha1: [u8; 20],
+}
+
+/// Options for loading a ROM.
+///
+/// Used by [`NdsRom::load_with_options`] and [`NdsRom::open_with_options`].
+pub struct RomOptions {
+    sram_strategy: Box<dyn SramStrategy>,
+}
+
+impl RomOptions {
+    /// Creates the default options.
+    pub fn new() -> RomOptions {
+        RomOptions {
+            sram_strategy: Box::new(MelonDsStrategy),
+        }
+    }
+
+    /// Sets the strategy used to guess the SRAM kind of ROMs not in the ROM
+    /// database.
+    ///
+    /// Defaults to [`MelonDsStrategy`].
+    pub fn with_sram_strategy<S>(mut self, strategy: S) -> RomOptions
+    where
+        S: SramStrategy + 'static,
+    {
+        self.sram_strategy = Box::new(strategy);
+        self
+    }
+}
+
+impl Default for RomOptions {
+    fn default() -> RomOptions {
+        RomOptions::new()
+    }
+}
+
+impl fmt::Debug for RomOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RomOptions").finish_non_exhaustive()
+    }
+}
+
+/// Trailing data after the used size of an overdumped ROM.
+///
+/// Returned by [`NdsRom::overdump_info`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OverdumpInfo {
+    /// The used size of the ROM, see [`NdsRom::overdump_info`].
+    pub used_size: usize,
+    /// The number of bytes after the used size.
+    pub trailing_size: usize,
+    /// Whether the trailing bytes are all `0x00` or all `0xFF`, ie. benign
+    /// padding rather than data.
+    pub is_padding: bool,
 }
 
 impl NdsRom {
     // TODO: Split up this function into smaller functions.
-    fn load_data(rom: Vec<u8>, rom_data_size: usize) -> NdsRom {
+    fn load_data(rom: Vec<u8>, rom_data_size: usize) -> Result<NdsRom, RomError> {
+        Self::parse(rom, rom_data_size, &RomOptions::new(), &mut Err)
+    }
+
+    /// Parses the ROM data, calling `recover` on recoverable errors to decide
+    /// whether to skip the affected part of the ROM or fail.
+    fn parse(
+        rom: Vec<u8>,
+        rom_data_size: usize,
+        options: &RomOptions,
+        recover: &mut dyn FnMut(RomError) -> Result<(), RomError>,
+    ) -> Result<NdsRom, RomError> {
+        if rom_data_size < NdsHeader::MIN_SIZE {
+            return Err(RomError::TooSmall {
+                size: rom_data_size,
+            });
+        }
+
         let rom = rom.into_boxed_slice();
         let rom_size = rom.len();
 
         let header = NdsHeader::read(&rom);
-        let banner = match header.banner_offset {
-            0 => None,
-            offset => Some(NdsBanner::read(&rom, offset as usize)),
+        if header.has_secure_area() && rom_size < 0x8000 {
+            recover(RomError::SecureAreaOutOfBounds {
+                offset: header.arm9_rom_offset,
+            })?;
+        }
+        let dsi_header = Self::read_dsi_header(&header, &rom, rom_data_size);
+        let banner = match Self::read_banner(&header, &rom) {
+            Ok(banner) => banner,
+            Err(err) => {
+                recover(err)?;
+                None
+            }
         };
 
+        let banner_truncated = Self::banner_truncated(&header, banner.as_ref(), rom_data_size);
+        if banner_truncated {
+            log::warn!(
+                "banner at offset {:#X} is truncated by the end of the ROM data at {:#X}",
+                header.banner_offset,
+                rom_data_size
+            );
+        }
+
         let game_code = header.game_code();
 
         let params = match RomParams::get(game_code) {
             Some(&params) => {
-                log::info!(
-                    "ROM entry: {} (SRAM {})",
-                    FileSize(params.rom_size as usize),
-                    params.sram_kind,
-                );
+                log::debug!("ROM entry: {}", params);
 
                 params
             }
-            None => {
-                let sram_kind = if header.is_homebrew() {
-                    // No SRAM for homebrew.
-                    SramKind::None
-                } else {
-                    // FIXME: We assume EEPROM with 64KB (same behaviour as melonDS).
-                    SramKind::Eeprom64KB
-                };
-
-                RomParams {
-                    rom_size: rom_size as u32,
-                    sram_kind,
-                }
-            }
+            None => RomParams::detect_with(&header, rom_size, &*options.sram_strategy),
         };
 
         if params.rom_size as usize != rom_data_size {
@@ -117,80 +303,263 @@ impl NdsRom {
         if header.is_dsi() {
             chip_id |= 0x08000000;
         }
-        if params.sram_kind.memory_kind() == MemoryKind::Nand {
+        if params.memory_kind() == MemoryKind::Nand {
             chip_id |= 0x48000000;
         } else if params.rom_size >= 128 * 1024 * 1024 {
             chip_id |= 0x80000000;
         }
 
-        log::info!("ROM chip ID: {:#010X}", chip_id);
-
-        // log::info!(
-        //     "Action Replay game ID: {}-{:08X}",
-        //     header.game_code,
-        //     crc::crc32(&rom[..0x200])
-        // );
+        log::trace!("ROM chip ID: {:#010X}", chip_id);
 
         let mut rom = NdsRom {
             rom,
             header,
+            dsi_header,
             banner,
             params,
             chip_id,
+            banner_truncated,
+            data_size: rom_data_size,
+            checksums: OnceLock::new(),
+            #[cfg(feature = "hashes")]
+            image_hashes: Default::default(),
         };
 
+        if let Some(info) = rom.overdump_info().filter(|info| !info.is_padding) {
+            log::warn!(
+                "overdumped ROM has {:#X} bytes of data after the used ROM size {:#X}",
+                info.trailing_size,
+                info.used_size
+            );
+        }
+
         rom.init_secure_area(game_code);
 
-        rom
+        Ok(rom)
+    }
+
+    fn read_dsi_header(header: &NdsHeader, rom: &[u8], rom_data_size: usize) -> Option<DsiHeader> {
+        if header.is_dsi() && rom_data_size >= DsiHeader::OFFSET + DsiHeader::SIZE {
+            Some(DsiHeader::read(rom))
+        } else {
+            None
+        }
+    }
+
+    fn read_banner(header: &NdsHeader, rom: &[u8]) -> Result<Option<NdsBanner>, RomError> {
+        let offset = match header.banner_offset {
+            0 => return Ok(None),
+            offset => offset,
+        };
+
+        // Only the banner data of the version has to be present, the rest is
+        // zero filled as in `NdsBanner::parse`.
+        let bytes = rom.get(offset as usize..).unwrap_or_default();
+        let size = match bytes {
+            &[lo, hi, ..] => BannerVersion::from(u16::from_le_bytes([lo, hi])).size(),
+            _ => NdsBanner::SIZE,
+        };
+        if bytes.len() < size {
+            return Err(RomError::BannerOutOfBounds { offset });
+        }
+
+        let mut padded = [0; NdsBanner::SIZE];
+        let len = bytes.len().min(NdsBanner::SIZE);
+        padded[..len].copy_from_slice(&bytes[..len]);
+
+        Ok(Some(NdsBanner::read(&padded, 0)))
+    }
+
+    fn banner_truncated(
+        header: &NdsHeader,
+        banner: Option<&NdsBanner>,
+        rom_data_size: usize,
+    ) -> bool {
+        // Trimmed ROMs may cut off the banner, which is then zero padded.
+        banner.is_some_and(|banner| {
+            header.banner_offset as usize + banner.version().size() > rom_data_size
+        })
     }
 
     fn init_secure_area(&mut self, game_code: u32) {
-        // The secure area exists if the ARM9 boot code ROM `offset` is located
-        // within `0x4000..0x8000`. If so, it will be loaded (by BIOS via KEY1
-        // encrypted commands) in 4KB portions, starting at `offset`, aligned to
-        // `0x1000`, up to address `0x7FFF`.
-        //
-        // The secure area size is thus `0x8000 - offset`, regardless of the
-        // ARM9 boot code size entry in the header.
-        //
-        // Note: The BIOS silently skips and ARM9 boot code when `offset < 0x4000`.
+        // The secure area is stored encrypted, as read from a cartridge, so
+        // decrypted ROM images are re-encrypted.
         //
         // <https://problemkaputt.de/gbatek.htm#dscartridgesecurearea>
-        // if header.has_secure_area() {
-        if let Some(secure_area) = self.secure_area_mut() {
-            // The first 8 bytes of the secure area contain the secure area ID,
-            // this ID is verified by the BIOS boot code, the ID value changes
-            // during the boot process:
-            //
-            //   "encryObj"                raw ID before encryption (raw ROM image)
-            //   (encrypted)               encrypted ID after encryption (encrypted ROM image)
-            //   "encryObj"                raw ID before encryption (verified by BIOS boot code)
-            //   0xE7FFDEFF, 0xE7FFDEFF    destroyed ID (overwritten by BIOS after verifying)
-            //
-            // If the decrypted ID does match, then the BIOS overwrites the first
-            // 8 bytes with 0xE7FFDEFF values (ie. only the ID is destroyed). If
-            // the ID doesn't match, then the first 0x800 bytes (2KB) are overwritten
-            // by 0xE7FFDEFF values.
-
-            // Magic value for destroyed secure area ID.
-            // This is a little endian u32 value.
-            const E7FFDEFF: [u8; 4] = [0xFF, 0xDE, 0xFF, 0xE7];
-
-            // Re-encrypt secure area if needed.
-            if secure_area[0..4] == E7FFDEFF && secure_area[0x10..0x14] != E7FFDEFF {
-                log::debug!("re-encrypting ROM secure area");
-
-                Key1::encrypt_secure_area(secure_area, game_code);
-            }
+        let arm9_rom_offset = self.header.arm9_rom_offset as usize;
+        if encrypt::reencrypt_secure_area(&mut self.rom, game_code, arm9_rom_offset) {
+            self.clear_checksums();
         }
     }
 
     /// Loads a ROM from a file.
-    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<NdsRom> {
+    ///
+    /// The ROM data is zero padded to a power of two, see [`open_exact`] to
+    /// avoid padding.
+    ///
+    /// With the `archives` feature, `.zip` and `.7z` archives containing a
+    /// single ROM are extracted transparently.
+    ///
+    /// [`open_exact`]: NdsRom::open_exact
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<NdsRom, OpenError> {
+        Self::open_with_progress(path, |_, _| {})
+    }
+
+    /// Loads a ROM from a file, reporting progress while reading.
+    ///
+    /// `on_progress` is called with `(bytes_read, total)` as the ROM data is
+    /// read, see [`open`] for details of loading.
+    ///
+    /// [`open`]: NdsRom::open
+    pub fn open_with_progress<P, F>(path: P, mut on_progress: F) -> Result<NdsRom, OpenError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(u64, u64),
+    {
+        Self::open_with(path.as_ref(), &RomOptions::new(), |_, read, len| {
+            on_progress(read, len)
+        })
+    }
+
+    /// Loads a ROM from a file, with the given options.
+    ///
+    /// See [`open`] for details of loading.
+    ///
+    /// [`open`]: NdsRom::open
+    pub fn open_with_options<P: AsRef<Path>>(
+        path: P,
+        options: &RomOptions,
+    ) -> Result<NdsRom, OpenError> {
+        Self::open_with(path.as_ref(), options, |_, _, _| {})
+    }
+
+    /// Loads a ROM from a file, hashing the file contents while reading.
+    ///
+    /// The file is only read once, see [`open`] for details of loading. For
+    /// archives, the extracted ROM is hashed.
+    ///
+    /// [`open`]: NdsRom::open
+    pub fn open_with_hashes<P: AsRef<Path>>(path: P) -> Result<(NdsRom, Hashes), OpenError> {
+        let mut crc32 = Crc32::new();
+        let mut sha1 = Sha1::new();
+
+        let rom = Self::open_with(path.as_ref(), &RomOptions::new(), |chunk, _, _| {
+            crc32.update(chunk);
+            sha1.update(chunk);
+        })?;
+
+        let hashes = Hashes {
+            crc32: !crc32.finish(),
+            sha1: sha1.finish(),
+        };
+        Ok((rom, hashes))
+    }
+
+    /// Loads a ROM from a file, then verifies it.
+    ///
+    /// The ROM is returned along with every issue found by [`verify`], so the
+    /// caller can decide whether to use a suspicious ROM. See [`open`] for
+    /// details of loading.
+    ///
+    /// [`verify`]: NdsRom::verify
+    /// [`open`]: NdsRom::open
+    pub fn open_validated<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<(NdsRom, Vec<VerifyIssue>), OpenError> {
+        let rom = Self::open(path)?;
+        let issues = rom.verify();
+        Ok((rom, issues))
+    }
+
+    fn open_with<F>(path: &Path, options: &RomOptions, on_chunk: F) -> Result<NdsRom, OpenError>
+    where
+        F: FnMut(&[u8], u64, u64),
+    {
+        #[allow(unused_mut)]
         let mut file = File::open(path)?;
 
-        let meta = file.metadata()?;
-        let len = meta.len() as usize;
+        #[cfg(feature = "archives")]
+        if let Some(kind) = archive::ArchiveKind::detect(path, &mut file)? {
+            return Self::read_padded(kind.extract(file)?, options, on_chunk);
+        }
+
+        let rom = Self::read_padded(file, options, on_chunk)?;
+
+        match detect_format(path) {
+            Some(RomFormat::Nds) if rom.header.dsi_compatibility() == DsiCompat::Exclusive => {
+                log::warn!("DSi exclusive ROM has an NDS file extension");
+            }
+            Some(RomFormat::Dsi) if !rom.is_dsi() => {
+                log::warn!("NDS ROM has a DSi file extension");
+            }
+            _ => {}
+        }
+
+        Ok(rom)
+    }
+
+    /// Loads a ROM from a file, without padding the ROM data.
+    ///
+    /// The ROM data is exactly the size of the file, so it must contain at
+    /// least the full header, and the banner and secure area (if any) must lie
+    /// within the file.
+    ///
+    /// With the `archives` feature, `.zip` and `.7z` archives containing a
+    /// single ROM are extracted transparently.
+    pub fn open_exact<P: AsRef<Path>>(path: P) -> Result<NdsRom, OpenError> {
+        #[allow(unused_mut)]
+        let mut file = File::open(path.as_ref())?;
+
+        #[cfg(feature = "archives")]
+        if let Some(kind) = archive::ArchiveKind::detect(path.as_ref(), &mut file)? {
+            return Self::load_exact(kind.extract(file)?.into_inner());
+        }
+
+        let mut rom = Vec::new();
+        file.read_to_end(&mut rom)?;
+
+        Self::load_exact(rom)
+    }
+
+    fn load_exact(rom: Vec<u8>) -> Result<NdsRom, OpenError> {
+        let len = rom.len();
+        if len < NdsHeader::SIZE {
+            return Err(RomError::TooSmall { size: len }.into());
+        }
+
+        Ok(Self::load_data(rom, len)?)
+    }
+
+    /// Loads a ROM from a reader.
+    ///
+    /// The ROM data is zero padded to a power of two.
+    pub fn from_reader<R: Read + Seek>(reader: R) -> Result<NdsRom, OpenError> {
+        Self::read_padded(reader, &RomOptions::new(), |_, _, _| {})
+    }
+
+    /// Reads a ROM, calling `on_chunk` with each chunk of data read, the total
+    /// bytes read and the size of the ROM.
+    fn read_padded<R, F>(
+        mut reader: R,
+        options: &RomOptions,
+        mut on_chunk: F,
+    ) -> Result<NdsRom, OpenError>
+    where
+        R: Read + Seek,
+        F: FnMut(&[u8], u64, u64),
+    {
+        // Read in chunks, so that progress is reported for large files.
+        const CHUNK_SIZE: usize = 1024 * 1024;
+
+        let start = reader.stream_position()?;
+        // The reader may be positioned past the end.
+        let len = reader.seek(SeekFrom::End(0))?.saturating_sub(start);
+        reader.seek(SeekFrom::Start(start))?;
+
+        if len > MAX_ROM_SIZE as u64 {
+            return Err(RomError::TooLarge { size: len }.into());
+        }
+        let len = len as usize;
 
         // ROM should be at least as large as the header.
         let mut rom_size = NdsHeader::SIZE;
@@ -199,27 +568,141 @@ impl NdsRom {
         }
 
         let mut rom = vec![0u8; rom_size];
-        // Read the ROM file into the buffer.
+        // Read the ROM into the buffer.
         {
             let mut buf = &mut rom[..];
+            let mut read = 0;
             loop {
-                match file.read(&mut buf) {
+                let chunk = buf.len().min(CHUNK_SIZE);
+                match reader.read(&mut buf[..chunk]) {
                     Ok(0) => break,
+                    Ok(n) if n > chunk => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "reader returned more bytes than requested",
+                        )
+                        .into());
+                    }
                     Ok(n) => {
-                        assert!(n <= buf.len());
-                        buf = &mut buf[n..];
+                        let (chunk, rest) = buf.split_at_mut(n);
+                        buf = rest;
+
+                        read += n;
+                        on_chunk(chunk, read as u64, len as u64);
                     }
                     Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
-                    Err(err) => return Err(err),
+                    Err(err) => return Err(err.into()),
                 }
             }
         }
 
-        Ok(Self::load_data(rom, len))
+        Ok(Self::parse(rom, len, options, &mut Err)?)
     }
 
     /// Loads a ROM from a byte array.
-    pub fn load(bytes: &[u8]) -> io::Result<NdsRom> {
+    ///
+    /// The ROM data is zero padded to a power of two.
+    pub fn load(bytes: &[u8]) -> Result<NdsRom, RomError> {
+        Self::load_data(Self::pad(bytes), bytes.len())
+    }
+
+    /// Loads a ROM from a byte array, with the given options.
+    ///
+    /// See [`load`] for details of loading.
+    ///
+    /// [`load`]: NdsRom::load
+    pub fn load_with_options(bytes: &[u8], options: &RomOptions) -> Result<NdsRom, RomError> {
+        Self::parse(Self::pad(bytes), bytes.len(), options, &mut Err)
+    }
+
+    /// Loads every ROM from a multi-cart image, with the offset of each ROM in
+    /// the image.
+    ///
+    /// Headers are found at `0x200` byte aligned offsets by the Nintendo logo
+    /// checksum, `0xCF56`, and a valid header checksum. Each ROM extends to
+    /// the next header, or the end of the image. Data within the used size of
+    /// a ROM is not scanned, so that ROMs embedded in its filesystem are not
+    /// found. ROMs which fail to load are skipped.
+    pub fn load_all(bytes: &[u8]) -> Vec<(usize, NdsRom)> {
+        const ALIGN: usize = 0x200;
+
+        let mut starts = Vec::new();
+        let mut offset = 0;
+        while let Some(header) = bytes.get(offset..).and_then(|b| NdsHeader::parse(b).ok()) {
+            if header.nintendo_logo_crc16 != verify::LOGO_CRC16
+                || header.header_crc16 != header.compute_header_crc16()
+            {
+                offset += ALIGN;
+                continue;
+            }
+
+            starts.push(offset);
+            match header
+                .used_rom_size()
+                .max(ALIGN)
+                .checked_next_multiple_of(ALIGN)
+            {
+                Some(used_size) => offset = offset.saturating_add(used_size),
+                None => break,
+            }
+        }
+
+        starts
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &start)| {
+                let end = starts.get(i + 1).copied().unwrap_or(bytes.len());
+                NdsRom::load(&bytes[start..end])
+                    .ok()
+                    .map(|rom| (start, rom))
+            })
+            .collect()
+    }
+
+    /// Loads as much of a ROM as possible from a byte array, collecting
+    /// diagnostics instead of failing on recoverable errors.
+    ///
+    /// The ROM data is zero padded to a power of two, as with [`load`]. Parts
+    /// of the ROM which cannot be loaded are skipped, see
+    /// [`LoadDiagnostic::Error`]. Integrity problems found by [`verify`] are
+    /// also included in the diagnostics.
+    ///
+    /// Returns `None` only if the ROM is too small to contain a header.
+    ///
+    /// [`load`]: NdsRom::load
+    /// [`verify`]: NdsRom::verify
+    pub fn try_load(bytes: &[u8]) -> (Option<NdsRom>, Vec<LoadDiagnostic>) {
+        let mut diagnostics = Vec::new();
+        let rom = Self::parse(
+            Self::pad(bytes),
+            bytes.len(),
+            &RomOptions::new(),
+            &mut |err| {
+                diagnostics.push(LoadDiagnostic::Error(err));
+                Ok(())
+            },
+        );
+
+        match rom {
+            Ok(rom) => {
+                if rom.banner_truncated {
+                    diagnostics.push(LoadDiagnostic::BannerTruncated {
+                        offset: rom.header.banner_offset,
+                    });
+                }
+                diagnostics.extend(rom.verify().into_iter().map(LoadDiagnostic::Issue));
+
+                (Some(rom), diagnostics)
+            }
+            Err(err) => {
+                diagnostics.push(LoadDiagnostic::Error(err));
+                (None, diagnostics)
+            }
+        }
+    }
+
+    /// Copies `bytes`, zero padded to a power of two.
+    fn pad(bytes: &[u8]) -> Vec<u8> {
         let len = bytes.len();
 
         // ROM should be at least as large as the header.
@@ -230,8 +713,290 @@ impl NdsRom {
 
         let mut rom = vec![0u8; rom_size];
         rom[..len].copy_from_slice(bytes);
+        rom
+    }
+
+    /// Returns the ROM data.
+    ///
+    /// The data is zero padded to a power of two, and at least the size of the
+    /// header, unless loaded with [`NdsRom::open_exact`].
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.rom
+    }
+
+    /// Consumes the ROM, returning the ROM data.
+    ///
+    /// The data is zero padded to a power of two, and at least the size of the
+    /// header, unless loaded with [`NdsRom::open_exact`].
+    #[inline]
+    pub fn into_inner(self) -> Box<[u8]> {
+        self.rom
+    }
 
-        Ok(Self::load_data(rom, len))
+    /// Consumes the ROM, returning it in an [`Arc`] for sharing between
+    /// threads.
+    ///
+    /// `NdsRom` is [`Send`] and [`Sync`], the lazily computed checksums are
+    /// synchronised.
+    #[inline]
+    pub fn into_arc(self) -> Arc<NdsRom> {
+        Arc::new(self)
+    }
+
+    /// Reads `len` bytes at a ROM address.
+    ///
+    /// Reads past the end of the ROM data, including the zero padding added
+    /// when loading, return `0xFF`, as for an unpopulated cartridge, so the
+    /// data is only copied if the read is out of bounds. At most
+    /// [`MAX_ROM_SIZE`] bytes are read.
+    pub fn read_at(&self, addr: u32, len: usize) -> Cow<'_, [u8]> {
+        let data = &self.rom[..self.data_size.min(self.rom.len())];
+        let len = len.min(MAX_ROM_SIZE);
+
+        let start = addr as usize;
+        match start.checked_add(len).and_then(|end| data.get(start..end)) {
+            Some(bytes) => Cow::Borrowed(bytes),
+            None => {
+                let mut bytes = vec![0xFF; len];
+                if let Some(rom) = data.get(start..) {
+                    bytes[..rom.len()].copy_from_slice(rom);
+                }
+                Cow::Owned(bytes)
+            }
+        }
+    }
+
+    /// Returns an iterator over the ROM data in blocks of `block_size` bytes,
+    /// with the ROM address of each block.
+    ///
+    /// This matches how the cartridge is read, eg. `0x200` byte blocks for
+    /// normal data reads. The zero padding added when loading is not ROM data,
+    /// as for [`read_at`], and the final block is shorter if the ROM data is
+    /// not a multiple of `block_size`.
+    ///
+    /// [`read_at`]: NdsRom::read_at
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is zero.
+    pub fn blocks(&self, block_size: usize) -> impl Iterator<Item = (u32, &[u8])> + '_ {
+        assert!(block_size != 0, "block size must be non-zero");
+
+        self.rom[..self.data_size.min(self.rom.len())]
+            .chunks(block_size)
+            .enumerate()
+            .map(move |(i, block)| ((i * block_size) as u32, block))
+    }
+
+    /// Edits the header, then fixes the header checksum and writes the header
+    /// back to the ROM data.
+    ///
+    /// Editing [`header`] directly does not update the ROM data.
+    ///
+    /// [`header`]: #structfield.header
+    pub fn with_header<F: FnOnce(&mut NdsHeader)>(&mut self, f: F) {
+        f(&mut self.header);
+
+        self.header.fix_crc();
+        self.rom[..NdsHeader::SIZE].copy_from_slice(&self.header.to_bytes());
+        self.clear_checksums();
+    }
+
+    /// Parses the header, DSi extended header and banner again from the ROM
+    /// data.
+    ///
+    /// This must be called after editing the header bytes in [`rom`] directly.
+    /// A banner extending past the end of the ROM is not loaded.
+    ///
+    /// [`rom`]: #structfield.rom
+    pub fn reparse_header(&mut self) {
+        self.header = NdsHeader::read(&self.rom);
+        self.dsi_header = Self::read_dsi_header(&self.header, &self.rom, self.data_size);
+        self.banner = Self::read_banner(&self.header, &self.rom).unwrap_or(None);
+        self.banner_truncated =
+            Self::banner_truncated(&self.header, self.banner.as_ref(), self.data_size);
+        self.clear_checksums();
+    }
+
+    /// Sets the banner title in the given language, updating the banner
+    /// checksums and writing the banner back to the ROM data.
+    ///
+    /// Only the data covered by the banner version is written.
+    pub fn set_banner_title(
+        &mut self,
+        lang: BannerLanguage,
+        text: &str,
+    ) -> Result<(), BannerEditError> {
+        let offset = self.header.banner_offset;
+        let banner = self.banner.as_mut().ok_or(BannerEditError::NoBanner)?;
+
+        let version = banner.version();
+        if !version.has_language(lang) {
+            return Err(BannerEditError::UnsupportedLanguage(lang));
+        }
+        let title = text.parse()?;
+
+        let start = offset as usize;
+        let size = version.size();
+        let dst = self
+            .rom
+            .get_mut(start..start.saturating_add(size))
+            .ok_or(BannerEditError::OutOfBounds { offset })?;
+
+        *banner.title_mut(lang) = title;
+        banner.fix_crc();
+        dst.copy_from_slice(&banner.as_bytes()[..size]);
+        self.clear_checksums();
+
+        Ok(())
+    }
+
+    /// Returns the name to display for the ROM.
+    ///
+    /// This is the first line of the [best title] of the banner, preferring the
+    /// [preferred language] of the market region, falling back to the header
+    /// game title if there is no banner or the titles are empty.
+    ///
+    /// [best title]: NdsBanner::best_title
+    /// [preferred language]: Region::preferred_language
+    pub fn display_name(&self) -> String {
+        let preferred = self
+            .header
+            .market_region()
+            .map_or(BannerLanguage::English, |region| {
+                region.preferred_language()
+            });
+
+        match self.banner.as_ref().and_then(|b| b.best_title(preferred)) {
+            // The title is trimmed, so the first line is not empty.
+            Some(title) => title.lines().next().unwrap_or_default().trim().to_owned(),
+            None => self.header.game_title.to_string_lossy().trim().to_owned(),
+        }
+    }
+
+    /// Returns the banner version, if the banner exists.
+    #[inline]
+    pub fn banner_version(&self) -> Option<BannerVersion> {
+        self.banner.as_ref().map(NdsBanner::version)
+    }
+
+    /// Returns the ROM address range of the banner, or `None` if there is no
+    /// banner.
+    ///
+    /// The range is sized by the version of the loaded banner, see
+    /// [`BannerVersion::size`]. If the banner was not loaded, this is the
+    /// header's [`NdsHeader::banner_range`].
+    pub fn banner_range(&self) -> Option<Range<usize>> {
+        let range = self.header.banner_range()?;
+        match &self.banner {
+            Some(banner) => Some(range.start..range.start.saturating_add(banner.version().size())),
+            None => Some(range),
+        }
+    }
+
+    /// Returns `true` if the banner extends past the end of the ROM data, eg.
+    /// if the ROM was trimmed.
+    ///
+    /// The missing part of the banner is zero filled.
+    #[inline]
+    pub fn is_banner_truncated(&self) -> bool {
+        self.banner_truncated
+    }
+
+    /// Returns information about the bytes after the used size of the ROM, or
+    /// `None` if the ROM data is not larger than the used size, or the used
+    /// size is zero (unknown, eg. for some homebrew).
+    ///
+    /// The used size is [`used_rom_size`] for NDS ROMs, and the total used
+    /// size including the DSi area (at `0x210`) for DSi ROMs. Only the ROM data
+    /// as loaded is considered, not the zero padding added when loading.
+    ///
+    /// # Notes
+    ///
+    /// Some NDS ROMs have an RSA signature of `0x88` bytes directly after the
+    /// used size, which is reported as trailing data.
+    ///
+    /// [`used_rom_size`]: NdsHeader::used_rom_size
+    pub fn overdump_info(&self) -> Option<OverdumpInfo> {
+        let used_size = self.used_size();
+        let data_size = self.data_size.min(self.rom.len());
+        if used_size == 0 {
+            return None;
+        }
+
+        let trailing = self.rom.get(used_size..data_size)?;
+        if trailing.is_empty() {
+            return None;
+        }
+
+        let is_padding = trailing.iter().all(|&b| b == 0x00) || trailing.iter().all(|&b| b == 0xFF);
+
+        Some(OverdumpInfo {
+            used_size,
+            trailing_size: trailing.len(),
+            is_padding,
+        })
+    }
+
+    /// Returns the response to the chip ID command in a phase of the cartridge
+    /// protocol.
+    ///
+    /// This is an alias for [`chip_id`], as the chip ID is the same in every
+    /// phase. Games read it in each phase to detect a cartridge being swapped.
+    ///
+    /// [`chip_id`]: #structfield.chip_id
+    ///
+    /// # Sources
+    ///
+    /// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgeprotocol>
+    #[inline]
+    pub fn chip_id_for_phase(&self, _phase: CartPhase) -> u32 {
+        self.chip_id
+    }
+
+    /// Returns a copy of the ROM data trimmed to the used size, removing the
+    /// unused space at the end of the ROM.
+    ///
+    /// The used size is as for [`overdump_info`]. If the used size is zero
+    /// (unknown) or past the end of the ROM data, the ROM data is returned
+    /// whole, without the zero padding added when loading.
+    ///
+    /// # Notes
+    ///
+    /// The RSA signature some NDS ROMs have after the used size is removed,
+    /// it is only used by DS Download Play.
+    ///
+    /// [`overdump_info`]: NdsRom::overdump_info
+    pub fn trim(&self) -> Vec<u8> {
+        let data_size = self.data_size.min(self.rom.len());
+        let size = match self.used_size() {
+            0 => data_size,
+            used_size => used_size.min(data_size),
+        };
+        self.rom[..size].to_vec()
+    }
+
+    /// The used size of the ROM, including the DSi area for DSi ROMs.
+    fn used_size(&self) -> usize {
+        match self.dsi_header {
+            Some(_) => LittleEndian::read_u32(&self.rom[0x210..0x214]) as usize,
+            None => self.header.used_rom_size(),
+        }
+    }
+
+    /// Decrypts the secure area disable field of the header, see
+    /// [`NdsHeader::secure_area_disable_marker`].
+    #[inline]
+    pub fn secure_area_disable_marker(&self) -> Option<SecureAreaDisable> {
+        self.header.secure_area_disable_marker()
+    }
+
+    /// Returns `true` if the ROM has an animated DSi icon.
+    #[inline]
+    pub fn has_animated_icon(&self) -> bool {
+        self.banner_version()
+            .is_some_and(BannerVersion::has_dsi_animation)
     }
 
     /// Returns `true` if the ROM a homebrew.
@@ -246,6 +1011,26 @@ impl NdsRom {
         self.header.is_dsi()
     }
 
+    /// Returns the DSi compatibility of the ROM.
+    #[inline]
+    pub fn dsi_compatibility(&self) -> DsiCompat {
+        self.header.dsi_compatibility()
+    }
+
+    /// Returns the console region the ROM is locked to, see
+    /// [`NdsHeader::region_locked_to`].
+    #[inline]
+    pub fn region_locked_to(&self) -> Option<NdsRegionLock> {
+        self.header.region_locked_to()
+    }
+
+    /// Returns `true` if the ROM boots on a console of the given region, see
+    /// [`NdsHeader::can_run_in_region`].
+    #[inline]
+    pub fn can_run_in_region(&self, region: NdsRegionLock) -> bool {
+        self.header.can_run_in_region(region)
+    }
+
     /// Returns `true` if the ROM has Infrared (IR).
     #[inline]
     pub fn has_ir(&self) -> bool {
@@ -264,26 +1049,321 @@ impl NdsRom {
         self.header.game_code()
     }
 
+    /// Returns the maker code as a `u16`.
+    #[inline]
+    pub fn maker_code_u16(&self) -> u16 {
+        self.header.maker_code_u16()
+    }
+
+    /// Returns a blank save for the cartridge, as a freshly erased SRAM chip
+    /// would read.
+    ///
+    /// The save is empty if the cartridge has no SRAM, see
+    /// [`SramKind::new_backing`].
+    pub fn new_save(&self) -> Vec<u8> {
+        self.params.sram_kind.new_backing()
+    }
+
+    /// Returns whether the ROM parameters detected from the ROM match those in
+    /// the ROM database.
+    ///
+    /// Only the ROM size is compared, the SRAM kind cannot be detected from
+    /// the ROM data.
+    ///
+    /// Returns `None` if the ROM is not in the database.
+    pub fn params_match_database(&self) -> Option<bool> {
+        let params = RomParams::get(self.game_code())?;
+
+        Some(params.rom_size == RomParams::detect(&self.header, self.rom.len()).rom_size)
+    }
+
+    /// Runs all available integrity checks, returning every issue found.
+    ///
+    /// This covers the header, logo, secure area and banner checksums, the ROM
+    /// database parameters, the ROM size and device capacity, and the ROM
+    /// ranges described by the header.
+    pub fn verify(&self) -> Vec<VerifyIssue> {
+        verify::verify(self)
+    }
+
+    /// Computes the Action Replay game ID.
+    ///
+    /// This is the CRC-32 of the header, usually displayed along with the
+    /// game code, eg. `ABCE-1234ABCD`.
+    pub fn action_replay_id(&self) -> u32 {
+        crc::crc32(&self.rom[..NdsHeader::SIZE])
+    }
+
     /// Returns a reference the secure area, if it exists.
+    ///
+    /// Returns `None` if the secure area extends past the end of the ROM data,
+    /// which is only possible for ROMs loaded with [`try_load`].
+    ///
+    /// [`try_load`]: NdsRom::try_load
     pub fn secure_area(&self) -> Option<&[u8]> {
         if self.header.has_secure_area() {
-            Some(&self.rom[(self.header.arm9_rom_offset as usize)..0x8000])
+            self.rom.get((self.header.arm9_rom_offset as usize)..0x8000)
         } else {
             None
         }
     }
 
     /// Returns a mutable reference the secure area, if it exists.
+    ///
+    /// Returns `None` if the secure area extends past the end of the ROM data,
+    /// see [`secure_area`](NdsRom::secure_area).
     pub fn secure_area_mut(&mut self) -> Option<&mut [u8]> {
         if self.header.has_secure_area() {
-            Some(&mut self.rom[(self.header.arm9_rom_offset as usize)..0x8000])
+            self.clear_checksums();
+            self.rom
+                .get_mut((self.header.arm9_rom_offset as usize)..0x8000)
         } else {
             None
         }
     }
 
+    /// Returns the ARM9 build info, located by the footer following the ARM9
+    /// binary.
+    ///
+    /// Returns `None` if the footer or build info is missing, eg. for homebrew.
+    pub fn arm9_build_info(&self) -> Option<Arm9BuildInfo> {
+        let range = self.header.arm9_rom_range();
+        let footer = self.rom.get(range.end..)?;
+        let arm9 = self.rom.get(range)?;
+
+        Arm9BuildInfo::read(arm9, footer)
+    }
+
+    /// Returns the filesystem, as described by the file name table (FNT).
+    pub fn filesystem(&self) -> Result<Filesystem<'_>, FilesystemError> {
+        Filesystem::new(
+            self.rom
+                .get(self.header.fnt_range())
+                .ok_or(FilesystemError::OutOfBounds)?,
+        )
+    }
+
+    /// Returns the ROM address range of a file, as described by the file
+    /// allocation table (FAT).
+    ///
+    /// Returns `None` if the file ID is not in the FAT, or the range lies
+    /// outside of the ROM.
+    pub fn file_range(&self, id: u16) -> Option<Range<usize>> {
+        let offset = (id as usize) * 8;
+        if offset + 8 > self.header.fat_size as usize {
+            return None;
+        }
+
+        let start = (self.header.fat_offset as usize).checked_add(offset)?;
+        let entry = self.rom.get(start..(start + 8))?;
+
+        let range = (LittleEndian::read_u32(&entry[0..4]) as usize)
+            ..(LittleEndian::read_u32(&entry[4..8]) as usize);
+        if range.start <= range.end && range.end <= self.rom.len() {
+            Some(range)
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the `(start, end)` ROM addresses of each entry
+    /// in the file allocation table (FAT), in file ID order.
+    ///
+    /// The entries are not validated, and a FAT extending past the end of the
+    /// ROM is truncated.
+    pub fn fat_entries(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.table(self.header.fat_range())
+            .chunks_exact(8)
+            .map(|entry| {
+                (
+                    LittleEndian::read_u32(&entry[0..4]),
+                    LittleEndian::read_u32(&entry[4..8]),
+                )
+            })
+    }
+
+    /// Returns an iterator over the entries in the ARM9 overlay table.
+    ///
+    /// An overlay table extending past the end of the ROM is truncated.
+    pub fn arm9_overlays(&self) -> impl Iterator<Item = Overlay> + '_ {
+        self.table(self.header.arm9_overlay_range())
+            .chunks_exact(Overlay::SIZE)
+            .map(Overlay::read)
+    }
+
+    /// Returns an iterator over the entries in the ARM7 overlay table.
+    ///
+    /// An overlay table extending past the end of the ROM is truncated.
+    pub fn arm7_overlays(&self) -> impl Iterator<Item = Overlay> + '_ {
+        self.table(self.header.arm7_overlay_range())
+            .chunks_exact(Overlay::SIZE)
+            .map(Overlay::read)
+    }
+
+    /// Returns the `(overlay ID, file ID, start, end)` of each overlay, with
+    /// the ROM address range of the overlay file from the file allocation
+    /// table (FAT).
+    ///
+    /// ARM9 overlays are listed first, followed by ARM7 overlays. Overlays
+    /// with a file ID that is not in the FAT are skipped.
+    pub fn overlay_file_map(&self) -> Vec<(u16, u16, u32, u32)> {
+        let fat = self.fat_entries().collect::<Vec<_>>();
+
+        self.arm9_overlays()
+            .chain(self.arm7_overlays())
+            .filter_map(|overlay| {
+                let (start, end) = *fat.get(overlay.file_id as usize)?;
+                Some((overlay.id as u16, overlay.file_id as u16, start, end))
+            })
+            .collect()
+    }
+
+    /// Returns the data of an overlay file.
+    ///
+    /// For a compressed overlay, the data is truncated to the
+    /// [`compressed_size`](Overlay::compressed_size) of the overlay.
+    ///
+    /// Returns `None` if the file ID is not in the FAT, or the file is out of
+    /// bounds.
+    pub fn overlay_data(&self, overlay: &Overlay) -> Option<&[u8]> {
+        let (start, end) = self.fat_entries().nth(overlay.file_id as usize)?;
+        let data = self.rom.get((start as usize)..(end as usize))?;
+
+        if overlay.is_compressed() {
+            data.get(..(overlay.compressed_size() as usize))
+        } else {
+            Some(data)
+        }
+    }
+
+    /// Returns the compression footer of an overlay, if it is compressed.
+    pub fn overlay_footer(&self, overlay: &Overlay) -> Option<BlzFooter> {
+        if !overlay.is_compressed() {
+            return None;
+        }
+        BlzFooter::read(self.overlay_data(overlay)?)
+    }
+
+    /// Returns the decompressed code of an overlay, as loaded into RAM.
+    ///
+    /// Returns `None` if the overlay data is missing or corrupt.
+    pub fn decompress_overlay(&self, overlay: &Overlay) -> Option<Vec<u8>> {
+        let data = self.overlay_data(overlay)?;
+
+        if overlay.is_compressed() {
+            decompress_blz(data)
+        } else {
+            Some(data.to_vec())
+        }
+    }
+
+    /// Returns the bytes of a table in ROM, truncated to the end of the ROM.
+    fn table(&self, range: Range<usize>) -> &[u8] {
+        let table = self.rom.get(range.start..).unwrap_or(&[]);
+        &table[..table.len().min(range.len())]
+    }
+
+    /// Maps how the space of the ROM is used by the regions described by the
+    /// header and the file allocation table (FAT), flagging overlaps, gaps and
+    /// regions past the end of the ROM.
+    pub fn layout_report(&self) -> LayoutReport {
+        layout::layout_report(self)
+    }
+
+    /// Returns the region containing a ROM offset, or `None` if the offset is
+    /// past the end of the ROM.
+    ///
+    /// The secure area takes precedence over the ARM9 boot code, and regions
+    /// described by the header over files. Offsets not in any region, such as
+    /// the padding at the end of the ROM, are [`RegionKind::Padding`].
+    pub fn region_of(&self, rom_offset: usize) -> Option<RegionKind> {
+        layout::region_of(self, rom_offset)
+    }
+
+    /// Replaces the contents of the file at `path` in place.
+    ///
+    /// The replacement data must be the same size as the existing file, as
+    /// the file allocation table (FAT) is left unchanged.
+    pub fn replace_file(&mut self, path: &str, data: &[u8]) -> Result<(), ReplaceError> {
+        let id = match self.filesystem()?.find(path) {
+            Some(Entry::File(_, id)) => id,
+            _ => return Err(ReplaceError::NotFound),
+        };
+
+        let range = self
+            .file_range(id)
+            .ok_or(ReplaceError::InvalidFatEntry { id })?;
+        if range.len() != data.len() {
+            return Err(ReplaceError::SizeMismatch {
+                expected: range.len(),
+                actual: data.len(),
+            });
+        }
+
+        self.rom[range].copy_from_slice(data);
+        self.clear_checksums();
+
+        Ok(())
+    }
+
+    /// Returns the state of the secure area ID, if the secure area exists.
+    pub fn secure_area_id(&self) -> Option<SecureAreaId> {
+        self.secure_area().map(SecureAreaId::of)
+    }
+
     /// Computes the secure area checksum, if it exists.
     pub fn compute_secure_area_crc16(&self) -> Option<u16> {
         self.secure_area().map(crc::crc16)
     }
+
+    /// Returns the checksums computed from the ROM data.
+    ///
+    /// The checksums are computed once, and cached until the ROM is edited by
+    /// [`with_header`], [`reparse_header`] or [`secure_area_mut`]. Editing
+    /// [`rom`] directly does not invalidate the cache.
+    ///
+    /// [`with_header`]: NdsRom::with_header
+    /// [`reparse_header`]: NdsRom::reparse_header
+    /// [`secure_area_mut`]: NdsRom::secure_area_mut
+    /// [`rom`]: #structfield.rom
+    pub fn checksums(&self) -> &Checksums {
+        self.checksums.get_or_init(|| Checksums {
+            header: self.header.compute_header_crc16(),
+            logo: self.header.compute_logo_crc16(),
+            secure_area: self.compute_secure_area_crc16(),
+        })
+    }
+
+    /// Clears the cached checksums and hashes, after the ROM data is edited.
+    fn clear_checksums(&mut self) {
+        self.checksums.take();
+        #[cfg(feature = "hashes")]
+        {
+            self.image_hashes = Default::default();
+        }
+    }
+
+    /// Computes the SHA-1 digest of the decrypted ROM, as used by some
+    /// verification databases.
+    ///
+    /// The hashed image is the ROM data as loaded, without the zero padding
+    /// added when loading, with the secure area in its canonical decrypted
+    /// form:
+    ///
+    /// - an encrypted secure area is decrypted, and its ID destroyed;
+    /// - a secure area that does not decrypt to a valid ID is hashed as is;
+    /// - a raw `"encryObj"` ID is destroyed;
+    /// - a destroyed secure area is hashed as is.
+    ///
+    /// A destroyed ID is the first 8 bytes set to `0xE7FFDEFF, 0xE7FFDEFF`, as
+    /// left by the BIOS after booting. The digest is thus the same whether the
+    /// ROM was dumped encrypted or decrypted. The ROM data is not modified.
+    ///
+    /// # Sources
+    ///
+    /// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgesecurearea>
+    #[cfg(feature = "hashes")]
+    pub fn decrypted_sha1(&self) -> [u8; 20] {
+        self.image_hashes(DatImage::Decrypted).sha1
+    }
 }
@@ -0,0 +1,82 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+/// An entry in an ARM9 or ARM7 overlay table (OVT).
+///
+/// # Sources
+///
+/// \[1\]: <https://problemkaputt.de/gbatek.htm#dscartridgenitroromandnitroarcfilesystems>
+///
+/// \[2\]: <https://github.com/devkitPro/ndstool/blob/master/source/overlay.h>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Overlay {
+    /// Overlay ID.
+    pub id: u32, // 0x00
+    /// RAM address the overlay is loaded to.
+    pub ram_address: u32, // 0x04
+    /// Size of the overlay in RAM, excluding BSS.
+    ///
+    /// This is the decompressed size, see [`compressed_size`] for the size of
+    /// the data in ROM.
+    ///
+    /// [`compressed_size`]: Overlay::compressed_size
+    pub ram_size: u32, // 0x08
+    /// Size of the BSS section.
+    pub bss_size: u32, // 0x0C
+    /// Start address of the static initialiser table.
+    pub static_init_start: u32, // 0x10
+    /// End address of the static initialiser table.
+    pub static_init_end: u32, // 0x14
+    /// File ID of the overlay, in the file allocation table (FAT).
+    pub file_id: u32, // 0x18
+    /// Compression flags.
+    ///
+    /// - Bit0-23: compressed size of the overlay
+    /// - Bit24: compressed
+    /// - Bit25: authenticated
+    pub flags: u32, // 0x1C
+}
+
+impl Overlay {
+    /// The size of an overlay table entry in bytes.
+    pub const SIZE: usize = 32;
+
+    pub(crate) fn read(entry: &[u8]) -> Overlay {
+        let word = |i: usize| LittleEndian::read_u32(&entry[(i * 4)..(i * 4 + 4)]);
+
+        Overlay {
+            id: word(0),
+            ram_address: word(1),
+            ram_size: word(2),
+            bss_size: word(3),
+            static_init_start: word(4),
+            static_init_end: word(5),
+            file_id: word(6),
+            flags: word(7),
+        }
+    }
+
+    /// Returns the compressed size of the overlay in bytes.
+    ///
+    /// This is the length of the compressed data in the overlay file,
+    /// including the [`BlzFooter`], which may be shorter than the file. The
+    /// decompressed size is [`ram_size`].
+    ///
+    /// [`BlzFooter`]: crate::nds::BlzFooter
+    /// [`ram_size`]: #structfield.ram_size
+    #[inline]
+    pub fn compressed_size(&self) -> u32 {
+        self.flags & 0x00FFFFFF
+    }
+
+    /// Returns `true` if the overlay is compressed.
+    #[inline]
+    pub fn is_compressed(&self) -> bool {
+        self.flags & (1 << 24) != 0
+    }
+
+    /// Returns `true` if the overlay is authenticated.
+    #[inline]
+    pub fn is_authenticated(&self) -> bool {
+        self.flags & (1 << 25) != 0
+    }
+}
@@ -0,0 +1,41 @@
+use common::str::Ascii;
+
+use crate::nds::info::{Region, SramKind};
+use crate::nds::NdsRom;
+
+/// A small summary of a ROM's identity, without the ROM data.
+///
+/// Useful for catalogue entries, cache keys and messages, where keeping the
+/// whole [`NdsRom`] around is wasteful.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RomSummary {
+    /// The game code.
+    pub game_code: Ascii<4>,
+    /// The maker code.
+    pub maker_code: Ascii<2>,
+    /// The display name, see [`NdsRom::display_name`].
+    pub title: String,
+    /// The market region, if known.
+    pub region: Option<Region>,
+    /// The SRAM kind.
+    pub sram_kind: SramKind,
+    /// The generated chip ID.
+    pub chip_id: u32,
+    /// The size of the ROM data before padding.
+    pub size: usize,
+}
+
+impl From<&NdsRom> for RomSummary {
+    fn from(rom: &NdsRom) -> RomSummary {
+        RomSummary {
+            game_code: rom.header.game_code,
+            maker_code: rom.header.maker_code,
+            title: rom.display_name(),
+            region: rom.header.market_region(),
+            sram_kind: rom.params.sram_kind,
+            chip_id: rom.chip_id,
+            size: rom.data_size,
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use std::fmt;
+
+use crate::nds::{header, NdsRom, RomParams, NINTENDO_LOGO};
+
+/// The checksum of the genuine Nintendo logo.
+pub(crate) const LOGO_CRC16: u16 = 0xCF56;
+
+/// The severity of a [`VerifyIssue`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// The ROM is unusual, but will likely still boot.
+    Warning,
+    /// The ROM will likely fail to boot, or is corrupt.
+    Error,
+}
+
+/// The kind of a [`VerifyIssue`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum IssueKind {
+    /// A stored checksum does not match the computed checksum.
+    Checksum,
+    /// A value does not match the expected value.
+    Mismatch,
+    /// Data does not match the expected data.
+    ///
+    /// The expected value is the size of the data, the actual value is the
+    /// offset of the first differing byte.
+    Differs,
+    /// A region ends past the end of the ROM data.
+    ///
+    /// The expected value is the size of the ROM data, excluding the padding
+    /// added when loading, the actual value is the end of the region.
+    OutOfBounds,
+}
+
+/// An integrity problem found by [`NdsRom::verify`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct VerifyIssue {
+    /// The severity of the issue.
+    pub severity: Severity,
+    /// The kind of the issue.
+    pub kind: IssueKind,
+    /// The name of the field with the issue.
+    pub field: &'static str,
+    /// The expected value.
+    pub expected: u64,
+    /// The actual value.
+    pub actual: u64,
+}
+
+impl VerifyIssue {
+    fn new(
+        severity: Severity,
+        kind: IssueKind,
+        field: &'static str,
+        expected: u64,
+        actual: u64,
+    ) -> VerifyIssue {
+        VerifyIssue {
+            severity,
+            kind,
+            field,
+            expected,
+            actual,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+impl fmt::Display for VerifyIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: ", self.severity, self.field)?;
+        match self.kind {
+            IssueKind::Checksum => write!(
+                f,
+                "checksum {:#06X} does not match {:#06X}",
+                self.actual, self.expected
+            ),
+            IssueKind::Mismatch => {
+                write!(f, "expected {:#X}, found {:#X}", self.expected, self.actual)
+            }
+            IssueKind::Differs => write!(f, "differs at offset {:#X}", self.actual),
+            IssueKind::OutOfBounds => write!(
+                f,
+                "ends at {:#X}, past the end of the ROM at {:#X}",
+                self.actual, self.expected
+            ),
+        }
+    }
+}
+
+pub(crate) fn verify(rom: &NdsRom) -> Vec<VerifyIssue> {
+    let mut issues = Vec::new();
+
+    verify_checksums(rom, &mut issues);
+    verify_size(rom, &mut issues);
+    verify_ranges(rom, &mut issues);
+
+    issues
+}
+
+fn verify_checksums(rom: &NdsRom, issues: &mut Vec<VerifyIssue>) {
+    let h = &rom.header;
+    let checksums = rom.checksums();
+
+    let crc = checksums.header;
+    if h.header_crc16 != crc {
+        issues.push(VerifyIssue::new(
+            Severity::Error,
+            IssueKind::Checksum,
+            "header_crc16",
+            crc as u64,
+            h.header_crc16 as u64,
+        ));
+    }
+
+    if h.nintendo_logo_crc16 != LOGO_CRC16 {
+        issues.push(VerifyIssue::new(
+            Severity::Error,
+            IssueKind::Checksum,
+            "nintendo_logo_crc16",
+            LOGO_CRC16 as u64,
+            h.nintendo_logo_crc16 as u64,
+        ));
+    }
+    let differs = h
+        .nintendo_logo
+        .iter()
+        .zip(&NINTENDO_LOGO)
+        .position(|(a, b)| a != b);
+    if let Some(offset) = differs {
+        // Homebrew is usually booted by a loader which skips the firmware.
+        let severity = if h.is_homebrew() {
+            Severity::Warning
+        } else {
+            Severity::Error
+        };
+        issues.push(VerifyIssue::new(
+            severity,
+            IssueKind::Differs,
+            "nintendo_logo",
+            NINTENDO_LOGO.len() as u64,
+            offset as u64,
+        ));
+    }
+
+    if let Some(crc) = checksums.secure_area {
+        if h.secure_area_crc16 != crc {
+            issues.push(VerifyIssue::new(
+                Severity::Error,
+                IssueKind::Checksum,
+                "secure_area_crc16",
+                crc as u64,
+                h.secure_area_crc16 as u64,
+            ));
+        }
+    }
+
+    if let Some(banner) = &rom.banner {
+        const FIELDS: [&str; 4] = [
+            "banner.crc16[0]",
+            "banner.crc16[1]",
+            "banner.crc16[2]",
+            "banner.crc16[3]",
+        ];
+
+        let crcs = banner.compute_crc16();
+        for ((field, &stored), crc) in FIELDS.into_iter().zip(&banner.crc16).zip(crcs) {
+            match crc {
+                Some(crc) if crc != stored => issues.push(VerifyIssue::new(
+                    Severity::Error,
+                    IssueKind::Checksum,
+                    field,
+                    crc as u64,
+                    stored as u64,
+                )),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn verify_size(rom: &NdsRom, issues: &mut Vec<VerifyIssue>) {
+    let h = &rom.header;
+
+    let capacity = header::fit_capacity(h.rom_size as u64);
+    if h.device_capacity < capacity {
+        issues.push(VerifyIssue::new(
+            Severity::Error,
+            IssueKind::Mismatch,
+            "device_capacity",
+            capacity as u64,
+            h.device_capacity as u64,
+        ));
+    }
+
+    // Padding is not dumped data, so a truncated dump must not pass.
+    let data_size = rom.data_size.min(rom.rom.len());
+    if h.rom_size as usize > data_size {
+        issues.push(VerifyIssue::new(
+            Severity::Error,
+            IssueKind::OutOfBounds,
+            "rom_size",
+            data_size as u64,
+            h.rom_size as u64,
+        ));
+    }
+
+    // See `NdsRom::params_match_database`.
+    if let Some(params) = RomParams::get(rom.game_code()) {
+        let detected = RomParams::detect(h, rom.rom.len());
+        if params.rom_size != detected.rom_size {
+            issues.push(VerifyIssue::new(
+                Severity::Warning,
+                IssueKind::Mismatch,
+                "params.rom_size",
+                params.rom_size as u64,
+                detected.rom_size as u64,
+            ));
+        }
+    }
+}
+
+fn verify_ranges(rom: &NdsRom, issues: &mut Vec<VerifyIssue>) {
+    let h = &rom.header;
+
+    let ranges = [
+        ("arm9_rom_offset", h.arm9_rom_range()),
+        ("arm7_rom_offset", h.arm7_rom_range()),
+        ("fnt_offset", h.fnt_range()),
+        ("fat_offset", h.fat_range()),
+        ("arm9_overlay_offset", h.arm9_overlay_range()),
+        ("arm7_overlay_offset", h.arm7_overlay_range()),
+        ("banner_offset", rom.banner_range().unwrap_or(0..0)),
+    ];
+
+    let data_size = rom.data_size.min(rom.rom.len()) as u64;
+    for (field, range) in ranges {
+        let end = range.end as u64;
+        if !range.is_empty() && end > data_size {
+            issues.push(VerifyIssue::new(
+                Severity::Error,
+                IssueKind::OutOfBounds,
+                field,
+                data_size,
+                end,
+            ));
+        }
+    }
+}
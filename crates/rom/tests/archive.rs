@@ -0,0 +1,65 @@
+#![cfg(feature = "archives")]
+
+use std::io::{Cursor, Write};
+use std::path::PathBuf;
+
+use rom::nds::{ArchiveError, NdsRom, OpenError};
+use zip::write::{FileOptions, ZipWriter};
+
+const TINY_FB: &[u8] = include_bytes!("../../../demo/TinyFB.nds");
+
+fn write_zip(name: &str, entries: &[&str]) -> PathBuf {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for entry in entries {
+        zip.start_file(*entry, FileOptions::default()).unwrap();
+        zip.write_all(TINY_FB).unwrap();
+    }
+    let bytes = zip.finish().unwrap().into_inner();
+
+    let path = std::env::temp_dir().join(format!("rsds-{}-{}", std::process::id(), name));
+    std::fs::write(&path, bytes).unwrap();
+    path
+}
+
+#[test]
+fn open_zip() {
+    let path = write_zip("single.zip", &["readme.txt", "TinyFB.nds"]);
+    let rom = NdsRom::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(rom.header.game_title, "NDS.TinyFB");
+}
+
+#[test]
+fn open_zip_dsi_extension() {
+    let path = write_zip("dsi.zip", &["TinyFB.srl"]);
+    let rom = NdsRom::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(rom.header.game_title, "NDS.TinyFB");
+}
+
+#[test]
+fn open_zip_by_magic() {
+    let path = write_zip("magic.bin", &["TinyFB.NDS"]);
+    let rom = NdsRom::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(rom.header.game_title, "NDS.TinyFB");
+}
+
+#[test]
+fn open_zip_candidates() {
+    let path = write_zip("none.zip", &["readme.txt"]);
+    let err = NdsRom::open(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(err, OpenError::Archive(ArchiveError::NoRom)));
+
+    let path = write_zip("multiple.zip", &["a.nds", "b.nds"]);
+    let err = NdsRom::open(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(
+        err,
+        OpenError::Archive(ArchiveError::MultipleRoms)
+    ));
+}
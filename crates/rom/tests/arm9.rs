@@ -0,0 +1,67 @@
+use rom::nds::{Arm9BuildInfo, NdsRom};
+
+const ARM9_OFFSET: usize = 0x200;
+const ARM9_SIZE: usize = 0x100;
+const BUILD_INFO_OFFSET: usize = 0x80;
+
+/// Builds a ROM with an ARM9 binary, footer and build info.
+fn build_rom(compressed_static_end: u32) -> Vec<u8> {
+    let mut rom = vec![0u8; 0x400];
+    rom[0x20..0x24].copy_from_slice(&(ARM9_OFFSET as u32).to_le_bytes());
+    rom[0x2C..0x30].copy_from_slice(&(ARM9_SIZE as u32).to_le_bytes());
+
+    let info = [
+        0x02000100u32,
+        0x02000110,
+        0x02000200,
+        0x02000300,
+        0x02000400,
+        compressed_static_end,
+        0x04027531,
+        0xDEC00621,
+        0x2106C0DE,
+    ];
+    let start = ARM9_OFFSET + BUILD_INFO_OFFSET;
+    for (i, word) in info.into_iter().enumerate() {
+        rom[(start + i * 4)..(start + i * 4 + 4)].copy_from_slice(&word.to_le_bytes());
+    }
+
+    let footer = ARM9_OFFSET + ARM9_SIZE;
+    rom[footer..(footer + 4)].copy_from_slice(&0xDEC00621u32.to_le_bytes());
+    rom[(footer + 4)..(footer + 8)].copy_from_slice(&(BUILD_INFO_OFFSET as u32).to_le_bytes());
+    rom
+}
+
+#[test]
+fn build_info() {
+    let rom = NdsRom::load(&build_rom(0x02000180)).unwrap();
+
+    let info = rom.arm9_build_info().unwrap();
+    assert_eq!(
+        info,
+        Arm9BuildInfo {
+            autoload_list_start: 0x02000100,
+            autoload_list_end: 0x02000110,
+            autoload_start: 0x02000200,
+            bss_start: 0x02000300,
+            bss_end: 0x02000400,
+            compressed_static_end: 0x02000180,
+            sdk_version: 0x04027531,
+        }
+    );
+    assert!(info.is_compressed());
+
+    let rom = NdsRom::load(&build_rom(0)).unwrap();
+    assert!(!rom.arm9_build_info().unwrap().is_compressed());
+}
+
+#[test]
+fn missing_footer() {
+    let mut rom = build_rom(0);
+    rom[ARM9_OFFSET + ARM9_SIZE] = 0x00;
+    let rom = NdsRom::load(&rom).unwrap();
+    assert_eq!(rom.arm9_build_info(), None);
+
+    let rom = NdsRom::load(include_bytes!("../../../demo/TinyFB.nds")).unwrap();
+    assert_eq!(rom.arm9_build_info(), None);
+}
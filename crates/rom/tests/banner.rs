@@ -0,0 +1,435 @@
+use common::str::Utf16;
+use rom::nds::{
+    BannerEditError, BannerError, BannerLanguage, BannerVersion, LoadDiagnostic, NdsBanner, NdsRom,
+};
+
+mod util;
+
+use util::RomBuilder;
+
+/// Builds a ROM with a banner, using `build` to fill in the banner bytes.
+fn build_rom(build: impl FnOnce(&mut [u8])) -> Vec<u8> {
+    RomBuilder::new().banner(0x0001, build).build()
+}
+
+/// Sets the icon pixel at `(x, y)` to palette `index`.
+fn set_pixel(banner: &mut [u8], x: usize, y: usize, index: u8) {
+    let tile = (y / 8) * 4 + x / 8;
+    let byte = &mut banner[0x20 + tile * 32 + (y % 8) * 4 + (x % 8) / 2];
+    if x & 1 == 0 {
+        *byte = (*byte & 0xF0) | index;
+    } else {
+        *byte = (*byte & 0x0F) | (index << 4);
+    }
+}
+
+#[test]
+fn icon_bitmap() {
+    let rom = build_rom(|banner| {
+        set_pixel(banner, 0, 0, 1);
+        set_pixel(banner, 9, 0, 2);
+        set_pixel(banner, 31, 31, 15);
+    });
+    let banner = NdsRom::load(&rom).unwrap().banner.unwrap();
+
+    let bitmap = banner.icon_bitmap();
+    assert_eq!(bitmap[0][0], 1);
+    assert_eq!(bitmap[0][9], 2);
+    assert_eq!(bitmap[31][31], 15);
+    assert_eq!(bitmap.iter().flatten().filter(|&&i| i != 0).count(), 3);
+}
+
+#[test]
+fn palette_rgba() {
+    let rom = build_rom(|banner| {
+        banner[0x220..0x222].copy_from_slice(&0x7FFFu16.to_le_bytes());
+        banner[0x222..0x224].copy_from_slice(&0x001Fu16.to_le_bytes());
+        banner[0x224..0x226].copy_from_slice(&0x03E0u16.to_le_bytes());
+        banner[0x23E..0x240].copy_from_slice(&0x4210u16.to_le_bytes());
+    });
+    let banner = NdsRom::load(&rom).unwrap().banner.unwrap();
+
+    let palette = banner.palette_rgba();
+    // Colour 0 is transparent, regardless of the palette entry.
+    assert_eq!(palette[0], [0, 0, 0, 0]);
+    assert_eq!(palette[1], [0xFF, 0, 0, 0xFF]);
+    assert_eq!(palette[2], [0, 0xFF, 0, 0xFF]);
+    assert_eq!(palette[15], [0x84, 0x84, 0x84, 0xFF]);
+}
+
+#[test]
+fn version() {
+    let rom = build_rom(|banner| banner[0..2].copy_from_slice(&0x0103u16.to_le_bytes()));
+
+    let rom = NdsRom::load(&rom).unwrap();
+    assert_eq!(rom.banner_version(), Some(BannerVersion::Dsi));
+    assert!(rom.has_animated_icon());
+
+    let version = rom.banner.unwrap().version();
+    assert_eq!(version, BannerVersion::Dsi);
+    assert!(version.has_chinese() && version.has_korean() && version.has_dsi_animation());
+
+    let version = BannerVersion::from(0x0002);
+    assert!(version.has_chinese() && !version.has_korean());
+
+    let version = BannerVersion::from(0x0004);
+    assert_eq!(version, BannerVersion::Unknown(0x0004));
+    assert_eq!(u16::from(version), 0x0004);
+    assert!(!version.has_chinese());
+}
+
+#[test]
+fn parse() {
+    let mut bytes = vec![0u8; 0x840];
+    bytes[0..2].copy_from_slice(&0x0001u16.to_le_bytes());
+    bytes[0x20] = 0x12;
+    bytes[0x340..0x346].copy_from_slice(b"T\0i\0n\0");
+
+    let banner = NdsBanner::parse(&bytes).unwrap();
+    assert_eq!(banner.version(), BannerVersion::Original);
+    assert_eq!(banner.icon[0], 0x12);
+    assert_eq!(*banner.title(BannerLanguage::English), "Tin");
+
+    assert_eq!(
+        NdsBanner::parse(&bytes[..0x83F]).unwrap_err(),
+        BannerError::TooSmall {
+            size: 0x83F,
+            expected: 0x840
+        }
+    );
+    assert_eq!(
+        NdsBanner::parse(&[0x01]).unwrap_err(),
+        BannerError::TooSmall {
+            size: 1,
+            expected: 2
+        }
+    );
+
+    bytes[0..2].copy_from_slice(&0x0003u16.to_le_bytes());
+    assert!(matches!(
+        NdsBanner::parse(&bytes),
+        Err(BannerError::TooSmall { .. })
+    ));
+
+    bytes[0..2].copy_from_slice(&0x0004u16.to_le_bytes());
+    assert_eq!(
+        NdsBanner::parse(&bytes).unwrap_err(),
+        BannerError::UnknownVersion(0x0004)
+    );
+}
+
+#[test]
+fn best_title() {
+    let set_title = |bytes: &mut [u8], lang: usize, title: &str| {
+        let offset = 0x240 + lang * 0x100;
+        let title = title.encode_utf16().flat_map(u16::to_le_bytes);
+        for (b, t) in bytes[offset..].iter_mut().zip(title) {
+            *b = t;
+        }
+    };
+
+    let mut bytes = vec![0u8; 0x0A40];
+    bytes[0..2].copy_from_slice(&0x0003u16.to_le_bytes());
+    set_title(&mut bytes, 1, " English ");
+    set_title(&mut bytes, 7, "Korean");
+    // A French title filled with `0xFFFF`.
+    bytes[0x440..0x540].fill(0xFF);
+    // A whitespace only German title.
+    set_title(&mut bytes, 3, "  ");
+
+    let banner = NdsBanner::parse(&bytes).unwrap();
+    let best = |lang| banner.best_title(lang);
+    assert_eq!(best(BannerLanguage::Korean).as_deref(), Some("Korean"));
+    assert_eq!(best(BannerLanguage::French).as_deref(), Some("English"));
+    assert_eq!(best(BannerLanguage::German).as_deref(), Some("English"));
+
+    // Falls back to the first title present.
+    set_title(&mut bytes, 1, "\0");
+    let banner = NdsBanner::parse(&bytes).unwrap();
+    assert_eq!(
+        banner.best_title(BannerLanguage::English).as_deref(),
+        Some("Korean")
+    );
+
+    // The Korean title is not covered by the original banner version.
+    bytes[0..2].copy_from_slice(&0x0001u16.to_le_bytes());
+    let banner = NdsBanner::parse(&bytes).unwrap();
+    assert_eq!(banner.best_title(BannerLanguage::Korean), None);
+}
+
+#[test]
+fn title() {
+    let title: Vec<u8> = "Title\nSub\nMaker"
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect();
+    let rom = build_rom(|banner| banner[0x340..(0x340 + title.len())].copy_from_slice(&title));
+    let banner = NdsRom::load(&rom).unwrap().banner.unwrap();
+
+    assert_eq!(
+        banner.title_english.to_string().unwrap(),
+        "Title\nSub\nMaker"
+    );
+    assert_eq!(banner.title_english, Utf16::<128>::from_le_bytes(&title));
+    assert_eq!(banner.title_japanese.len(), 0);
+}
+
+// Loading a banner is not yet supported on big-endian targets.
+#[cfg(target_endian = "little")]
+#[test]
+fn title_little_endian() {
+    // "\u{3042}\u{00E9}\n2", as stored in the ROM, which decodes differently
+    // if the code units are read in the wrong byte order.
+    const TITLE: [u8; 8] = [0x42, 0x30, 0xE9, 0x00, 0x0A, 0x00, b'2', 0x00];
+
+    let rom = build_rom(|banner| banner[0x340..0x348].copy_from_slice(&TITLE));
+    let rom = NdsRom::load(&rom).unwrap();
+
+    let title = &rom.banner.as_ref().unwrap().title_english;
+    assert_eq!(&*title.units(), &[0x3042, 0x00E9, 0x000A, 0x0032]);
+    assert_eq!(*title, "\u{3042}\u{00E9}\n2");
+    assert_eq!(*title, Utf16::<128>::from_le_bytes(&TITLE));
+    assert_eq!(rom.display_name(), "\u{3042}\u{00E9}");
+}
+
+#[test]
+fn display_name() {
+    let title: Vec<u8> = " Title \nSub"
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect();
+    let rom = build_rom(|banner| banner[0x340..(0x340 + title.len())].copy_from_slice(&title));
+    assert_eq!(NdsRom::load(&rom).unwrap().display_name(), "Title");
+
+    // Falls back to the game title if the banner title is empty.
+    let mut rom = build_rom(|_| {});
+    rom[0..6].copy_from_slice(b"GAME  ");
+    assert_eq!(NdsRom::load(&rom).unwrap().display_name(), "GAME");
+
+    // Prefers the title in the language of the region.
+    let mut rom = build_rom(|banner| {
+        banner[0x240..0x244].copy_from_slice(&[0x72, 0x30, 0x6B, 0x30]);
+        banner[0x340..0x344].copy_from_slice(&[b'E', 0, b'N', 0]);
+    });
+    rom[0x0C..0x10].copy_from_slice(b"ABCJ");
+    assert_eq!(
+        NdsRom::load(&rom).unwrap().display_name(),
+        "\u{3072}\u{306B}"
+    );
+    rom[0x0C..0x10].copy_from_slice(b"ABCE");
+    assert_eq!(NdsRom::load(&rom).unwrap().display_name(), "EN");
+    // Chinese titles are missing from the original banner version.
+    rom[0x0C..0x10].copy_from_slice(b"ABCC");
+    assert_eq!(NdsRom::load(&rom).unwrap().display_name(), "EN");
+
+    // Skips unused titles filled with 0xFFFF.
+    let mut rom = build_rom(|banner| {
+        banner[0x240..0x340].fill(0xFF);
+        banner[0x340..0x344].copy_from_slice(&[b'E', 0, b'N', 0]);
+    });
+    rom[0x0C..0x10].copy_from_slice(b"ABCJ");
+    assert_eq!(NdsRom::load(&rom).unwrap().display_name(), "EN");
+}
+
+#[cfg(feature = "ansi")]
+#[test]
+fn icon_ansi() {
+    let rom = build_rom(|banner| {
+        // Palette entry 1 is pure red.
+        banner[0x222..0x224].copy_from_slice(&0x001Fu16.to_le_bytes());
+        set_pixel(banner, 0, 0, 1);
+        set_pixel(banner, 1, 1, 1);
+    });
+    let banner = NdsRom::load(&rom).unwrap().banner.unwrap();
+
+    let ansi = banner.icon_ansi();
+    let lines: Vec<_> = ansi.lines().collect();
+    assert_eq!(lines.len(), 16);
+    assert!(
+        lines[0].starts_with("\x1b[0;38;2;255;0;0m\u{2580}\x1b[0;38;2;255;0;0m\u{2584}\x1b[0m ")
+    );
+    assert!(lines[1]
+        .chars()
+        .all(|c| c == ' ' || !c.is_alphabetic() || c == 'm'));
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn icon_png() {
+    let rom = build_rom(|banner| {
+        // Palette entry 1 is pure blue.
+        banner[0x222..0x224].copy_from_slice(&0x7C00u16.to_le_bytes());
+        set_pixel(banner, 1, 0, 1);
+    });
+    let banner = NdsRom::load(&rom).unwrap().banner.unwrap();
+
+    let mut png = Vec::new();
+    banner.write_icon_png(&mut png).unwrap();
+
+    let mut reader = png::Decoder::new(&png[..]).read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    reader.next_frame(&mut pixels).unwrap();
+
+    assert_eq!(&pixels[0..4], [0, 0, 0, 0]);
+    assert_eq!(&pixels[4..8], [0, 0, 0xFF, 0xFF]);
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn dsi_icon_apng() {
+    let rom = build_rom(|banner| {
+        banner[0..2].copy_from_slice(&0x0103u16.to_le_bytes());
+        // Bitmap 0 for 10 frames, then bitmap 1 flipped horizontally for 20 frames.
+        banner[0x2340..0x2342].copy_from_slice(&0x000Au16.to_le_bytes());
+        banner[0x2342..0x2344].copy_from_slice(&0x4114u16.to_le_bytes());
+    });
+    let banner = NdsRom::load(&rom).unwrap().banner.unwrap();
+
+    let mut png = Vec::new();
+    banner.write_dsi_icon_apng(&mut png).unwrap();
+
+    let reader = png::Decoder::new(&png[..]).read_info().unwrap();
+    let animation = reader.info().animation_control.unwrap();
+    assert_eq!(animation.num_frames, 2);
+    assert_eq!(animation.num_plays, 0);
+}
+
+#[test]
+fn truncated() {
+    // The banner starts at `0x200`, so trimming keeps the original banner data
+    // but cuts off the DSi icon animation.
+    const TRIMMED_SIZE: usize = 0x2100;
+
+    let rom = RomBuilder::new().banner(0x0103, |_| {}).build();
+    assert!(!NdsRom::load(&rom).unwrap().is_banner_truncated());
+
+    let (trimmed, diagnostics) = NdsRom::try_load(&rom[..TRIMMED_SIZE]);
+    assert!(diagnostics.contains(&LoadDiagnostic::BannerTruncated { offset: 0x200 }));
+    assert!(trimmed.unwrap().is_banner_truncated());
+
+    let rom = NdsRom::load(&rom[..TRIMMED_SIZE]).unwrap();
+    assert!(rom.is_banner_truncated());
+    assert_eq!(rom.banner.unwrap().dsi_sequence, [0; 64]);
+
+    // Only the banner data of the version needs to be present.
+    let rom = RomBuilder::new().banner(0x0001, |_| {}).build();
+    let rom = NdsRom::load(&rom[..TRIMMED_SIZE]).unwrap();
+    assert!(!rom.is_banner_truncated());
+}
+
+#[test]
+fn banner_at_end() {
+    // The v1 banner ends the ROM, well within the full `NdsBanner::SIZE` of
+    // the padded ROM end.
+    let rom = RomBuilder::new()
+        .banner(0x0001, |banner| banner[0x340] = b'T')
+        .build();
+    let rom = &rom[..0x200 + 0x840];
+
+    let rom = NdsRom::load(rom).unwrap();
+    assert_eq!(rom.rom.len(), 0x1000);
+    assert!(!rom.is_banner_truncated());
+
+    let banner = rom.banner.unwrap();
+    assert_eq!(banner.version(), BannerVersion::Original);
+    assert_eq!(banner.title_english.units()[0], u16::from(b'T'));
+    assert_eq!(banner.dsi_sequence, [0; 64]);
+}
+
+#[test]
+fn icon_crc32() {
+    let a = build_rom(|banner| set_pixel(banner, 0, 0, 1));
+    let b = build_rom(|banner| {
+        set_pixel(banner, 0, 0, 1);
+        // Titles are not part of the icon.
+        banner[0x340] = b'T';
+    });
+    let c = build_rom(|banner| set_pixel(banner, 0, 0, 2));
+
+    let crc = |rom: &[u8]| NdsRom::load(rom).unwrap().banner.unwrap().icon_crc32();
+    assert_eq!(crc(&a), crc(&b));
+    assert_ne!(crc(&a), crc(&c));
+
+    let banner = NdsRom::load(&a).unwrap().banner.unwrap();
+    assert_eq!(banner.dsi_icon_crc32(), None);
+
+    let rom = build_rom(|banner| banner[0..2].copy_from_slice(&0x0103u16.to_le_bytes()));
+    let banner = NdsRom::load(&rom).unwrap().banner.unwrap();
+    assert!(banner.dsi_icon_crc32().is_some());
+}
+
+#[test]
+fn set_banner_title() {
+    let mut rom = NdsRom::load(&build_rom(|_| {})).unwrap();
+
+    rom.set_banner_title(BannerLanguage::French, "Titre\nÉditeur")
+        .unwrap();
+    assert_eq!(
+        rom.banner.unwrap().title(BannerLanguage::French),
+        "Titre\nÉditeur"
+    );
+
+    // The banner in the ROM data is updated, with valid checksums.
+    let reloaded = NdsRom::load(rom.as_bytes()).unwrap();
+    let banner = reloaded.banner.unwrap();
+    assert_eq!(banner.title_french, "Titre\nÉditeur");
+    assert_eq!(banner.compute_crc16()[0], Some(banner.crc16[0]));
+
+    assert_eq!(
+        rom.set_banner_title(BannerLanguage::Korean, "Title"),
+        Err(BannerEditError::UnsupportedLanguage(BannerLanguage::Korean))
+    );
+    let err = rom
+        .set_banner_title(BannerLanguage::English, &"a".repeat(129))
+        .unwrap_err();
+    assert!(matches!(err, BannerEditError::TooLong(err) if err.encoded_len() == 129));
+
+    let mut rom = NdsRom::load(&RomBuilder::new().build()).unwrap();
+    assert_eq!(
+        rom.set_banner_title(BannerLanguage::English, "Title"),
+        Err(BannerEditError::NoBanner)
+    );
+}
+
+#[test]
+fn fix_crc() {
+    let rom = build_rom(|banner| banner[0..2].copy_from_slice(&0x0002u16.to_le_bytes()));
+    let mut banner = NdsRom::load(&rom).unwrap().banner.unwrap();
+    banner.crc16[3] = 0x1234;
+
+    banner.icon[0] = 0x11;
+    *banner.title_mut(BannerLanguage::Chinese) = "Title".parse().unwrap();
+    assert_ne!(banner.compute_crc16()[1], Some(banner.crc16[1]));
+
+    banner.fix_crc();
+    let crcs = banner.compute_crc16();
+    assert_eq!(crcs[0], Some(banner.crc16[0]));
+    assert_eq!(crcs[1], Some(banner.crc16[1]));
+    // Not covered by the banner version.
+    assert_eq!((banner.crc16[2], banner.crc16[3]), (0, 0x1234));
+}
+
+#[test]
+fn reparse_banner() {
+    let bytes = build_rom(|_| {});
+    let mut rom = NdsRom::load(&bytes).unwrap();
+    let banner_offset = rom.header.banner_offset as usize;
+    assert!(rom.banner.is_some());
+
+    rom.rom[0x68..0x6C].fill(0);
+    rom.reparse_header();
+    assert!(rom.banner.is_none());
+
+    // Trimmed to cut off the banner, which was not loaded.
+    let mut trimmed = vec![0; 0x4100];
+    trimmed[..0x200].copy_from_slice(&bytes[..0x200]);
+    trimmed[0x68..0x6C].fill(0);
+    trimmed[0x4000..].copy_from_slice(&bytes[banner_offset..banner_offset + 0x100]);
+    let mut rom = NdsRom::load(&trimmed).unwrap();
+    assert!(rom.banner.is_none());
+
+    rom.rom[0x68..0x6C].copy_from_slice(&0x4000u32.to_le_bytes());
+    rom.reparse_header();
+    assert!(rom.banner.is_some());
+    assert!(rom.is_banner_truncated());
+}
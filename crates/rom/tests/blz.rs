@@ -0,0 +1,48 @@
+use rom::nds::{decompress_blz, BlzFooter};
+
+/// `b"abc"` repeated 10 times: 3 literals, then copies of 18 and 9 bytes.
+const COMPRESSED: [u8; 16] = [
+    0x00, 0x60, 0x00, 0xF0, b'a', b'b', b'c', 0x18, // Compressed data.
+    0x10, 0x00, 0x00, 0x08, 0x0E, 0x00, 0x00, 0x00, // Footer.
+];
+
+#[test]
+fn footer() {
+    let footer = BlzFooter::read(&COMPRESSED).unwrap();
+    assert_eq!(footer.compressed_len, 0x10);
+    assert_eq!(footer.header_len, 8);
+    assert_eq!(footer.extra_len, 14);
+    assert_eq!(footer.decompressed_len(COMPRESSED.len()), 30);
+
+    assert_eq!(BlzFooter::read(&COMPRESSED[..4]), None);
+}
+
+#[test]
+fn decompress() {
+    assert_eq!(decompress_blz(&COMPRESSED).unwrap(), b"abc".repeat(10));
+
+    // Data before the compressed region is stored uncompressed.
+    let mut data = b"xyz".to_vec();
+    data.extend_from_slice(&COMPRESSED);
+    let mut expected = b"xyz".to_vec();
+    expected.extend_from_slice(&b"abc".repeat(10));
+    assert_eq!(decompress_blz(&data).unwrap(), expected);
+}
+
+#[test]
+fn corrupt() {
+    // Decompressed length too large.
+    let mut data = COMPRESSED;
+    data[12] = 0x20;
+    assert_eq!(decompress_blz(&data), None);
+
+    // Compressed length past the start of the data.
+    let mut data = COMPRESSED;
+    data[8] = 0x20;
+    assert_eq!(decompress_blz(&data), None);
+
+    // Copy from past the end of the decompressed data.
+    let mut data = COMPRESSED;
+    data[1] = 0x6F;
+    assert_eq!(decompress_blz(&data), None);
+}
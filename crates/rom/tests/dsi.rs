@@ -0,0 +1,96 @@
+use rom::nds::{detect_format, DsiCompat, NdsRom, RomFormat, TwlCapabilities};
+
+mod util;
+
+use util::RomBuilder;
+
+/// Builds a DSi exclusive ROM with an extended header.
+fn build_rom() -> Vec<u8> {
+    RomBuilder::new()
+        .header(|header| {
+            // Unit code.
+            header[0x012] = 0x03;
+            header[0x238..0x23C].copy_from_slice(&0x8000u32.to_le_bytes());
+            header[0x23C..0x240].copy_from_slice(&0x4000u32.to_le_bytes());
+        })
+        .build()
+}
+
+#[test]
+fn save_sizes() {
+    let rom = NdsRom::load(&build_rom()).unwrap();
+    let dsi_header = rom.dsi_header.unwrap();
+
+    assert_eq!(dsi_header.public_save_size(), 0x8000);
+    assert_eq!(dsi_header.private_save_size(), 0x4000);
+}
+
+#[test]
+fn nds_rom() {
+    let mut rom = build_rom();
+    rom[0x012] = 0x00;
+    let rom = NdsRom::load(&rom).unwrap();
+
+    assert!(rom.dsi_header.is_none());
+}
+
+#[test]
+fn truncated() {
+    let rom = NdsRom::load(&build_rom()[..0x800]).unwrap();
+
+    assert!(rom.is_dsi());
+    assert!(rom.dsi_header.is_none());
+}
+
+#[test]
+fn format_from_extension() {
+    assert_eq!(detect_format("game.nds"), Some(RomFormat::Nds));
+    assert_eq!(detect_format("game.IDS"), Some(RomFormat::Nds));
+    assert_eq!(detect_format("dir/game.dsi"), Some(RomFormat::Dsi));
+    assert_eq!(detect_format("game.srl"), Some(RomFormat::Srl));
+    assert_eq!(detect_format("game.zip"), None);
+    assert_eq!(detect_format("game"), None);
+}
+
+#[test]
+fn dsi_compatibility() {
+    let mut rom = build_rom();
+    for (unit_code, compat) in [
+        (0x00, DsiCompat::None),
+        (0x02, DsiCompat::Enhanced),
+        (0x03, DsiCompat::Exclusive),
+    ] {
+        rom[0x012] = unit_code;
+        let rom = NdsRom::load(&rom).unwrap();
+
+        assert_eq!(rom.dsi_compatibility(), compat);
+        assert_eq!(rom.is_dsi(), compat != DsiCompat::None);
+    }
+}
+
+#[test]
+fn twl_capabilities() {
+    let mut rom = build_rom();
+    rom[0x00C..0x010].copy_from_slice(b"KABE");
+
+    let caps = NdsRom::load(&rom).unwrap().header.twl_capabilities();
+    assert!(!caps.ds_mode && caps.dsi_mode && !caps.is_hybrid());
+    assert!(!caps.dsi_tsc_mode && !caps.system_title);
+
+    rom[0x012] = 0x02;
+    rom[0x01C] = 0x04;
+    rom[0x00C..0x010].copy_from_slice(b"HNAE");
+    let caps = NdsRom::load(&rom).unwrap().header.twl_capabilities();
+    assert!(caps.is_hybrid() && caps.dsi_tsc_mode && caps.system_title);
+
+    // DS only ROMs have no DSi capabilities.
+    rom[0x012] = 0x00;
+    let caps = NdsRom::load(&rom).unwrap().header.twl_capabilities();
+    assert_eq!(
+        caps,
+        TwlCapabilities {
+            ds_mode: true,
+            ..Default::default()
+        }
+    );
+}
@@ -0,0 +1,352 @@
+use rom::nds::{
+    Entry, FilesystemError, IssueKind, NdsRom, Overlay, RegionKind, ReplaceError, Severity,
+    VerifyIssue, ROOT_DIR_ID,
+};
+
+mod util;
+
+use util::RomBuilder;
+
+// As laid out by `RomBuilder`.
+const FNT_OFFSET: usize = 0x200;
+const FAT_OFFSET: usize = 0x240;
+
+const FILES: [&[u8]; 3] = [b"AAAA", b"CCCC", b"BBBBBB"];
+
+/// Builds a ROM with the following filesystem:
+///
+/// ```text
+/// /
+/// ├── a.bin    (file 0)
+/// ├── data/
+/// │   └── b.bin    (file 2)
+/// └── c.bin    (file 1)
+/// ```
+fn build_rom() -> Vec<u8> {
+    RomBuilder::new()
+        .file("a.bin", FILES[0])
+        .file("data/b.bin", FILES[2])
+        .file("c.bin", FILES[1])
+        .build()
+}
+
+#[test]
+fn list_root() {
+    let rom = NdsRom::load(&build_rom()).unwrap();
+    let fs = rom.filesystem().unwrap();
+
+    assert_eq!(fs.dir_count(), 2);
+
+    let root = fs.root();
+    assert!(root.parent().is_none());
+
+    let entries: Vec<_> = root.entries().collect();
+    assert_eq!(
+        entries,
+        [
+            Entry::File(b"a.bin", 0),
+            Entry::Dir(b"data", 0xF001),
+            Entry::File(b"c.bin", 1)
+        ]
+    );
+}
+
+#[test]
+fn descend() {
+    let rom = NdsRom::load(&build_rom()).unwrap();
+    let fs = rom.filesystem().unwrap();
+
+    let data = fs.dir(0xF001).unwrap();
+    assert_eq!(data.parent().unwrap().id(), ROOT_DIR_ID);
+
+    let entries: Vec<_> = data.entries().collect();
+    assert_eq!(entries, [Entry::File(b"b.bin", 2)]);
+
+    assert!(fs.dir(0xF002).is_none());
+}
+
+#[test]
+fn empty_fnt() {
+    let rom = NdsRom::load(&[0u8; 0x200]).unwrap();
+    let fs = rom.filesystem().unwrap();
+
+    assert_eq!(fs.dir_count(), 0);
+    assert_eq!(fs.root().entries().count(), 0);
+}
+
+#[test]
+fn find() {
+    let rom = NdsRom::load(&build_rom()).unwrap();
+    let fs = rom.filesystem().unwrap();
+
+    assert_eq!(fs.find("/data/b.bin"), Some(Entry::File(b"b.bin", 2)));
+    assert_eq!(fs.find("data"), Some(Entry::Dir(b"data", 0xF001)));
+    assert_eq!(fs.find("a.bin/b.bin"), None);
+    assert_eq!(fs.find("missing"), None);
+}
+
+#[test]
+fn fat_entries() {
+    let rom = NdsRom::load(&build_rom()).unwrap();
+
+    let entries = rom.fat_entries().collect::<Vec<_>>();
+    assert_eq!(entries, [(0x260, 0x264), (0x264, 0x268), (0x268, 0x26E)]);
+
+    for (id, &(start, end)) in entries.iter().enumerate() {
+        assert_eq!(
+            rom.file_range(id as u16),
+            Some((start as usize)..(end as usize))
+        );
+    }
+}
+
+#[test]
+fn overlays() {
+    // Overlay 0 in file 1, overlay 1 in a file missing from the FAT.
+    let mut ovt = [0u8; 2 * Overlay::SIZE];
+    ovt[0x04..0x08].copy_from_slice(&0x0200_0000u32.to_le_bytes());
+    ovt[0x18..0x1C].copy_from_slice(&1u32.to_le_bytes());
+    ovt[0x1C..0x20].copy_from_slice(&0x0100_0004u32.to_le_bytes());
+    ovt[0x20..0x24].copy_from_slice(&1u32.to_le_bytes());
+    ovt[0x38..0x3C].copy_from_slice(&9u32.to_le_bytes());
+
+    let mut rom = NdsRom::load(
+        &RomBuilder::new()
+            .file("ovt.bin", &ovt)
+            .file("ov0.bin", b"CODE")
+            .build(),
+    )
+    .unwrap();
+    let range = rom.file_range(0).unwrap();
+    rom.with_header(|h| {
+        h.arm9_overlay_offset = range.start as u32;
+        h.arm9_overlay_size = ovt.len() as u32;
+    });
+
+    let overlays = rom.arm9_overlays().collect::<Vec<_>>();
+    assert_eq!(overlays.len(), 2);
+    assert_eq!(overlays[0].ram_address, 0x0200_0000);
+    assert!(overlays[0].is_compressed() && !overlays[0].is_authenticated());
+    assert_eq!(overlays[0].compressed_size(), 4);
+    assert_eq!(overlays[1].id, 1);
+    assert_eq!(rom.arm7_overlays().count(), 0);
+
+    let (start, end) = rom.fat_entries().nth(1).unwrap();
+    assert_eq!(rom.overlay_file_map(), [(0, 1, start, end)]);
+}
+
+#[test]
+fn compressed_overlay() {
+    // `b"abc"` repeated 10 times, see `blz.rs`.
+    const COMPRESSED: [u8; 16] = [
+        0x00, 0x60, 0x00, 0xF0, b'a', b'b', b'c', 0x18, 0x10, 0x00, 0x00, 0x08, 0x0E, 0x00, 0x00,
+        0x00,
+    ];
+
+    // Overlay 0 in file 1, compressed, with padding after the compressed data.
+    let mut ovt = [0u8; Overlay::SIZE];
+    ovt[0x08..0x0C].copy_from_slice(&30u32.to_le_bytes());
+    ovt[0x18..0x1C].copy_from_slice(&1u32.to_le_bytes());
+    ovt[0x1C..0x20].copy_from_slice(&0x0100_0010u32.to_le_bytes());
+    let mut file = COMPRESSED.to_vec();
+    file.extend_from_slice(&[0xFF; 4]);
+
+    let mut rom = NdsRom::load(
+        &RomBuilder::new()
+            .file("ovt.bin", &ovt)
+            .file("ov0.bin", &file)
+            .build(),
+    )
+    .unwrap();
+    let range = rom.file_range(0).unwrap();
+    rom.with_header(|h| {
+        h.arm9_overlay_offset = range.start as u32;
+        h.arm9_overlay_size = ovt.len() as u32;
+    });
+
+    let overlay = rom.arm9_overlays().next().unwrap();
+    assert_eq!(rom.overlay_data(&overlay), Some(&COMPRESSED[..]));
+    assert_eq!(rom.overlay_footer(&overlay).unwrap().extra_len, 14);
+
+    let code = rom.decompress_overlay(&overlay).unwrap();
+    assert_eq!(code.len(), overlay.ram_size as usize);
+    assert_eq!(code, b"abc".repeat(10));
+}
+
+#[test]
+fn layout_report() {
+    let rom = NdsRom::load(&build_rom()).unwrap();
+
+    let report = rom.layout_report();
+    assert!(report.is_consistent());
+
+    let kinds = report
+        .regions
+        .iter()
+        .map(|region| region.kind)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            RegionKind::Header,
+            RegionKind::Fnt,
+            RegionKind::Fat,
+            RegionKind::File(0),
+            RegionKind::File(1),
+            RegionKind::File(2),
+        ]
+    );
+    assert_eq!(report.gaps, [0x22B..0x240, 0x258..0x260]);
+}
+
+#[test]
+fn region_of() {
+    let rom = NdsRom::load(&build_rom()).unwrap();
+    let report = rom.layout_report();
+
+    // Every region maps back to its kind.
+    for region in &report.regions {
+        assert_eq!(rom.region_of(region.start as usize), Some(region.kind));
+        assert_eq!(rom.region_of(region.end as usize - 1), Some(region.kind));
+    }
+    for gap in &report.gaps {
+        assert_eq!(rom.region_of(gap.start as usize), Some(RegionKind::Padding));
+    }
+
+    let end = rom.rom.len();
+    assert_eq!(rom.region_of(end - 1), Some(RegionKind::Padding));
+    assert_eq!(rom.region_of(end), None);
+}
+
+#[test]
+fn layout_report_overlap() {
+    let mut rom = build_rom();
+    // Point file 1 into file 0.
+    rom[FAT_OFFSET + 8..FAT_OFFSET + 12].copy_from_slice(&0x262u32.to_le_bytes());
+    // Extend file 2 past the end of the ROM.
+    rom[FAT_OFFSET + 20..FAT_OFFSET + 24].copy_from_slice(&0x1000u32.to_le_bytes());
+    let rom = NdsRom::load(&rom).unwrap();
+
+    let report = rom.layout_report();
+    assert!(!report.is_consistent());
+
+    let overlaps = report
+        .overlaps
+        .iter()
+        .map(|(a, b)| (a.kind, b.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(overlaps, [(RegionKind::File(0), RegionKind::File(1))]);
+    assert_eq!(report.out_of_bounds.len(), 1);
+    assert_eq!(report.out_of_bounds[0].kind, RegionKind::File(2));
+}
+
+#[test]
+fn layout_report_banner_version() {
+    let mut rom = RomBuilder::new()
+        .file("a.bin", FILES[0])
+        .banner(0x0001, |_| {})
+        .build();
+    let banner_offset = u32::from_le_bytes(rom[0x68..0x6C].try_into().unwrap());
+
+    let fat_offset = u32::from_le_bytes(rom[0x48..0x4C].try_into().unwrap()) as usize;
+
+    // Place file 0 directly after the version 1 banner.
+    let start = banner_offset + 0x840;
+    rom[fat_offset..fat_offset + 4].copy_from_slice(&start.to_le_bytes());
+    rom[fat_offset + 4..fat_offset + 8].copy_from_slice(&(start + 4).to_le_bytes());
+    let rom = NdsRom::load(&rom).unwrap();
+
+    let banner = banner_offset as usize;
+    assert_eq!(rom.banner_range(), Some(banner..banner + 0x840));
+    assert_eq!(rom.region_of(banner + 0x840), Some(RegionKind::File(0)));
+
+    let report = rom.layout_report();
+    assert!(report.is_consistent(), "{:?}", report.overlaps);
+}
+
+#[test]
+fn layout_report_overlaps_many() {
+    let mut rom = build_rom();
+    // Point every file at the same data.
+    for id in 0..3 {
+        let entry = FAT_OFFSET + 8 * id;
+        rom[entry..entry + 4].copy_from_slice(&0x260u32.to_le_bytes());
+        rom[entry + 4..entry + 8].copy_from_slice(&0x264u32.to_le_bytes());
+    }
+    let rom = NdsRom::load(&rom).unwrap();
+
+    let overlaps = rom
+        .layout_report()
+        .overlaps
+        .iter()
+        .map(|(a, b)| (a.kind, b.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        overlaps,
+        [
+            (RegionKind::File(0), RegionKind::File(1)),
+            (RegionKind::File(0), RegionKind::File(2)),
+        ]
+    );
+}
+
+#[test]
+fn verify_truncated() {
+    let mut image = RomBuilder::new()
+        .header(|header| header[0x80..0x84].copy_from_slice(&0x3800u32.to_le_bytes()))
+        .file("a.bin", &[0x5A; 0x3000])
+        .build();
+    // Truncated, then padded to 0x4000 bytes when loaded.
+    image.truncate(0x2100);
+    let rom = NdsRom::load(&image).unwrap();
+    assert_eq!(rom.rom.len(), 0x4000);
+
+    let issues = rom.verify();
+    assert!(issues.contains(&VerifyIssue {
+        severity: Severity::Error,
+        kind: IssueKind::OutOfBounds,
+        field: "rom_size",
+        expected: 0x2100,
+        actual: 0x3800,
+    }));
+}
+
+#[test]
+fn replace_file() {
+    let mut rom = NdsRom::load(&build_rom()).unwrap();
+
+    rom.replace_file("/data/b.bin", b"bbbbbb").unwrap();
+    let range = rom.file_range(2).unwrap();
+    assert_eq!(&rom.rom[range], b"bbbbbb");
+    assert_eq!(&rom.rom[rom.file_range(1).unwrap()], b"CCCC");
+
+    assert_eq!(
+        rom.replace_file("c.bin", b"CC"),
+        Err(ReplaceError::SizeMismatch {
+            expected: 4,
+            actual: 2
+        })
+    );
+    assert_eq!(rom.replace_file("data", b""), Err(ReplaceError::NotFound));
+}
+
+#[test]
+fn obfuscated_fnt() {
+    let mut bytes = build_rom();
+    // Root subtable offset within the main table.
+    bytes[FNT_OFFSET..(FNT_OFFSET + 4)].copy_from_slice(&8u32.to_le_bytes());
+    let rom = NdsRom::load(&bytes).unwrap();
+    assert_eq!(rom.filesystem().unwrap_err(), FilesystemError::Obfuscated);
+
+    let mut bytes = build_rom();
+    // Implausible directory count.
+    bytes[(FNT_OFFSET + 6)..(FNT_OFFSET + 8)].copy_from_slice(&0xFFFFu16.to_le_bytes());
+    let rom = NdsRom::load(&bytes).unwrap();
+    assert_eq!(rom.filesystem().unwrap_err(), FilesystemError::Obfuscated);
+
+    let mut bytes = build_rom();
+    // FNT past the end of the ROM.
+    bytes[0x44..0x48].copy_from_slice(&0x10000u32.to_le_bytes());
+    let rom = NdsRom::load(&bytes).unwrap();
+    assert_eq!(rom.filesystem().unwrap_err(), FilesystemError::OutOfBounds);
+}
@@ -0,0 +1,125 @@
+//! Feeds random headers over short buffers, checking that loading and the
+//! parsers never panic. The cargo-fuzz seed corpus is replayed here too.
+
+use rom::nds::NdsRom;
+
+/// A small deterministic xorshift PRNG.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    /// Returns a table or binary size, mostly small enough to fit in the ROM.
+    fn size(&mut self) -> u32 {
+        match self.below(8) {
+            0 => self.next() as u32,
+            1 => 0x8000 + self.below(0x1000) as u32,
+            _ => self.below(0x800) as u32,
+        }
+    }
+
+    /// Returns a ROM offset, biased towards the interesting boundaries.
+    fn offset(&mut self, len: usize) -> u32 {
+        match self.below(6) {
+            0 => self.next() as u32,
+            1 => 0x4000 + self.below(0x4000) as u32,
+            2 => 0x8000 - self.below(0x10) as u32,
+            3 => len as u32 - self.below(0x20).min(len as u64) as u32,
+            4 => 0,
+            _ => self.below(len as u64 + 1) as u32,
+        }
+    }
+}
+
+fn random_rom(rng: &mut Rng) -> Vec<u8> {
+    let len = match rng.below(3) {
+        0 => 0x160 + rng.below(0xA0) as usize,
+        1 => 0x200 + rng.below(0x8000) as usize,
+        _ => 0x4000 + rng.below(0x8000) as usize,
+    };
+
+    let mut rom = (0..len).map(|_| rng.next() as u8).collect::<Vec<_>>();
+    // Offset/size pairs from the ARM9 ROM offset to the ARM7 overlay size,
+    // then the banner offset.
+    for field in (0x20..0x60).step_by(8).chain([0x68]) {
+        let offset = rng.offset(len);
+        rom[field..(field + 4)].copy_from_slice(&offset.to_le_bytes());
+        if field != 0x68 {
+            let size = rng.size();
+            rom[(field + 4)..(field + 8)].copy_from_slice(&size.to_le_bytes());
+        }
+    }
+    if rng.below(2) == 0 {
+        // Destroyed secure area ID, to trigger re-encryption.
+        let offset = u32::from_le_bytes(rom[0x20..0x24].try_into().unwrap()) as usize;
+        if let Some(id) = rom.get_mut(offset..(offset + 8)) {
+            id.copy_from_slice(&[0xFF, 0xDE, 0xFF, 0xE7, 0xFF, 0xDE, 0xFF, 0xE7]);
+        }
+    }
+
+    rom
+}
+
+fn exercise(rom: &NdsRom) {
+    let _ = rom.display_name();
+    let _ = rom.verify();
+    let _ = rom.layout_report();
+    let _ = rom.checksums();
+    let _ = rom.secure_area_id();
+    let _ = rom.arm9_build_info();
+    let _ = rom.overlay_file_map();
+    // Random tables can hold thousands of overlays, a handful is enough.
+    for overlay in rom.arm9_overlays().chain(rom.arm7_overlays()).take(64) {
+        let _ = rom.overlay_footer(&overlay);
+        let _ = rom.decompress_overlay(&overlay);
+    }
+    if let Ok(fs) = rom.filesystem() {
+        for id in 0..fs.dir_count() {
+            if let Some(dir) = fs.dir(0xF000 + id) {
+                let _ = dir.entries().count();
+            }
+        }
+        let _ = fs.find("a/b/c");
+    }
+}
+
+#[test]
+fn random_headers() {
+    let mut rng = Rng(0x0123_4567_89AB_CDEF);
+
+    for _ in 0..500 {
+        let bytes = random_rom(&mut rng);
+
+        if let Ok(rom) = NdsRom::load(&bytes) {
+            exercise(&rom);
+        }
+        if let (Some(rom), _) = NdsRom::try_load(&bytes) {
+            exercise(&rom);
+        }
+    }
+}
+
+#[test]
+fn fuzz_corpus() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/corpus/load");
+
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let bytes = std::fs::read(entry.unwrap().path()).unwrap();
+
+        if let Ok(rom) = NdsRom::load(&bytes) {
+            exercise(&rom);
+        }
+        if let (Some(rom), _) = NdsRom::try_load(&bytes) {
+            exercise(&rom);
+        }
+    }
+}
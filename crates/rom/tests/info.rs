@@ -0,0 +1,216 @@
+use std::collections::{BTreeMap, HashMap};
+
+use common::util::FileSize;
+
+use rom::nds::{
+    BannerLanguage, MemoryKind, NdsHeader, NdsRom, Region, RomOptions, RomParams,
+    SizeHeuristicStrategy, SramKind, SramStrategy,
+};
+
+#[test]
+fn sram_kind_ordered_by_size() {
+    let mut kinds = [
+        SramKind::Nand64MB,
+        SramKind::Flash512KB,
+        SramKind::None,
+        SramKind::Eeprom8KB,
+        SramKind::Eeprom512B,
+    ];
+    kinds.sort();
+
+    assert_eq!(
+        kinds,
+        [
+            SramKind::None,
+            SramKind::Eeprom512B,
+            SramKind::Eeprom8KB,
+            SramKind::Flash512KB,
+            SramKind::Nand64MB,
+        ]
+    );
+    assert!(SramKind::Flash1MB > SramKind::Eeprom128KB);
+}
+
+#[test]
+fn histogram() {
+    let kinds = [
+        SramKind::Flash512KB,
+        SramKind::Eeprom64KB,
+        SramKind::Flash512KB,
+    ];
+
+    let mut by_sram = BTreeMap::new();
+    let mut by_memory = HashMap::new();
+    for kind in kinds {
+        *by_sram.entry(kind).or_insert(0) += 1;
+        *by_memory.entry(kind.memory_kind()).or_insert(0) += 1;
+    }
+
+    assert_eq!(
+        by_sram.into_iter().collect::<Vec<_>>(),
+        [(SramKind::Eeprom64KB, 1), (SramKind::Flash512KB, 2)]
+    );
+    assert_eq!(by_memory[&MemoryKind::Flash], 2);
+    assert_eq!(by_memory[&MemoryKind::EepromRegular], 1);
+}
+
+/// Builds a non-homebrew ROM with the given game code and size.
+fn build_rom(game_code: &[u8; 4], size: usize) -> NdsRom {
+    let mut rom = vec![0u8; size];
+    rom[0x0C..0x10].copy_from_slice(game_code);
+    rom[0x20..0x24].copy_from_slice(&0x4000u32.to_le_bytes());
+    NdsRom::load(&rom).unwrap()
+}
+
+#[test]
+fn params_match_database() {
+    // "YCUE" is listed as 4MB with EEPROM 64KB.
+    let rom = build_rom(b"YCUE", 0x400000);
+    assert_eq!(rom.params_match_database(), Some(true));
+
+    let rom = build_rom(b"YCUE", 0x800000);
+    assert_eq!(rom.params_match_database(), Some(false));
+
+    // "BOOP" is listed with EEPROM 8KB, which is not detected.
+    let rom = build_rom(b"BOOP", 0x400000);
+    assert_eq!(rom.params_match_database(), Some(true));
+    assert!(rom
+        .verify()
+        .iter()
+        .all(|issue| !issue.field.starts_with("params.")));
+
+    let rom = build_rom(b"####", 0x8000);
+    assert_eq!(rom.params_match_database(), None);
+}
+
+#[test]
+fn numeric_codes() {
+    let mut rom = build_rom(b"YCUE", 0x400000);
+    rom.header.maker_code.buf = *b"01";
+
+    assert_eq!(rom.game_code(), u32::from_le_bytes(*b"YCUE"));
+    assert_eq!(rom.header.maker_code_u16(), 0x3130);
+    assert_eq!(rom.maker_code_u16(), u16::from_le_bytes(*b"01"));
+}
+
+#[test]
+fn spi_metadata() {
+    assert_eq!(MemoryKind::EepromSmall.address_bytes(), Some(1));
+    assert_eq!(MemoryKind::EepromRegular.address_bytes(), Some(2));
+    assert_eq!(MemoryKind::Flash.address_bytes(), Some(3));
+    assert_eq!(MemoryKind::Nand.address_bytes(), None);
+    assert_eq!(MemoryKind::None.page_size(), None);
+
+    assert_eq!(SramKind::Eeprom512B.page_size(), Some(16));
+    assert_eq!(SramKind::Eeprom8KB.page_size(), Some(32));
+    assert_eq!(SramKind::Eeprom64KB.page_size(), Some(128));
+    assert_eq!(SramKind::Eeprom128KB.page_size(), Some(256));
+    assert_eq!(SramKind::Eeprom128KB.address_bytes(), Some(3));
+    assert_eq!(SramKind::Flash512KB.page_size(), Some(256));
+    assert_eq!(SramKind::Nand16MB.page_size(), None);
+
+    assert_eq!(SramKind::Flash256KB.jedec_id(), Some([0x20, 0x40, 0x12]));
+    assert_eq!(SramKind::Flash1MB.jedec_id(), Some([0x20, 0x40, 0x14]));
+    // The capacity byte is log2 of the size in bytes.
+    for kind in [
+        SramKind::Flash256KB,
+        SramKind::Flash512KB,
+        SramKind::Flash1MB,
+    ] {
+        let [_, _, capacity] = kind.jedec_id().unwrap();
+        assert_eq!(1 << capacity, kind.size() as u32);
+    }
+    assert_eq!(SramKind::Eeprom64KB.jedec_id(), None);
+    assert_eq!(SramKind::Nand64MB.jedec_id(), None);
+}
+
+#[test]
+fn enumerate_codes() {
+    let regions = rom::nds::all_regions().collect::<Vec<_>>();
+    assert!(regions.windows(2).all(|w| w[0].0 < w[1].0));
+    assert!(regions.contains(&(b'C', "CHN")));
+
+    let makers = rom::nds::all_makers().collect::<Vec<_>>();
+    assert!(makers.windows(2).all(|w| w[0].0 < w[1].0));
+    assert_eq!(makers[0], ("01", "Nintendo"));
+}
+
+#[test]
+fn regions() {
+    for (code, name) in rom::nds::all_regions() {
+        let region = Region::from_code(code).unwrap();
+        assert_eq!(region.code(), code);
+        assert_eq!(region.name(), name);
+    }
+    assert_eq!(Region::from_code(b'A'), None);
+    assert_eq!(Region::from_code(b'V'), Some(Region::EuropeV));
+    assert_eq!(Region::EuropeZ.name(), "EUU");
+
+    assert_eq!(Region::Usa.preferred_language(), BannerLanguage::English);
+    assert_eq!(Region::Japan.preferred_language(), BannerLanguage::Japanese);
+    assert_eq!(Region::Germany.preferred_language(), BannerLanguage::German);
+    assert_eq!(Region::Russia.preferred_language(), BannerLanguage::English);
+}
+
+#[test]
+fn sram_strategy() {
+    struct Fixed;
+
+    impl SramStrategy for Fixed {
+        fn guess(&self, _header: &NdsHeader, _rom_size: usize) -> SramKind {
+            SramKind::Flash256KB
+        }
+    }
+
+    let mut rom = vec![0u8; 0x2000000];
+    rom[0x0C..0x10].copy_from_slice(b"ZZZE");
+    rom[0x20..0x24].copy_from_slice(&0x4000u32.to_le_bytes());
+
+    let load = |options: RomOptions| NdsRom::load_with_options(&rom, &options).unwrap();
+    assert_eq!(
+        load(RomOptions::new()).params.sram_kind,
+        SramKind::Eeprom64KB
+    );
+    assert_eq!(
+        load(RomOptions::new().with_sram_strategy(SizeHeuristicStrategy))
+            .params
+            .sram_kind,
+        SramKind::Flash512KB
+    );
+    assert_eq!(
+        load(RomOptions::new().with_sram_strategy(Fixed))
+            .params
+            .sram_kind,
+        SramKind::Flash256KB
+    );
+
+    // ROMs in the database are unaffected.
+    rom[0x0C..0x10].copy_from_slice(b"YCUE");
+    let options = RomOptions::new().with_sram_strategy(Fixed);
+    let rom = NdsRom::load_with_options(&rom, &options).unwrap();
+    assert_eq!(rom.params.sram_kind, SramKind::Eeprom64KB);
+}
+
+#[test]
+fn new_save() {
+    let rom = build_rom(b"YCUE", 0x400000);
+    let save = rom.new_save();
+    assert_eq!(save.len(), 64 * 1024);
+    assert!(save.iter().all(|&b| b == 0xFF));
+
+    assert!(build_rom(b"####", 0x8000).new_save().is_empty());
+    assert_eq!(SramKind::Flash256KB.new_backing(), vec![0xFF; 256 * 1024]);
+    assert_eq!(MemoryKind::None.erased_byte(), 0x00);
+}
+
+#[test]
+fn params_display() {
+    let params = RomParams {
+        rom_size: 0x400000,
+        sram_kind: SramKind::Eeprom64KB,
+    };
+    assert_eq!(
+        params.to_string(),
+        format!("{} (SRAM EEPROM 64KB)", FileSize(0x400000))
+    );
+}
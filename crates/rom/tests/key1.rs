@@ -0,0 +1,257 @@
+use std::num::NonZeroU8;
+
+use rom::nds::encrypt::{reencrypt_secure_area, Key1};
+
+mod util;
+
+use crate::util::DESTROYED_ID;
+
+const GAME_CODE: u32 = u32::from_le_bytes(*b"ABCE");
+
+fn encrypt(key1: &Key1) -> [u8; 8] {
+    let mut block = *b"encryObj";
+    key1.encrypt_block(&mut block);
+    block
+}
+
+#[test]
+fn init_levels() {
+    let level = |n| Key1::init(GAME_CODE, NonZeroU8::new(n).unwrap());
+
+    assert_eq!(encrypt(&level(1)), encrypt(&Key1::init1(GAME_CODE)));
+    assert_eq!(encrypt(&level(2)), encrypt(&Key1::init2(GAME_CODE)));
+    assert_eq!(encrypt(&level(3)), encrypt(&Key1::init3(GAME_CODE)));
+
+    assert_ne!(encrypt(&level(1)), encrypt(&level(2)));
+    assert_ne!(encrypt(&level(3)), encrypt(&level(4)));
+}
+
+#[test]
+fn round_trip() {
+    let key1 = Key1::init3(GAME_CODE);
+
+    let mut block = *b"encryObj";
+    key1.encrypt_block(&mut block);
+    assert_ne!(&block, b"encryObj");
+    key1.decrypt_block(&mut block);
+    assert_eq!(&block, b"encryObj");
+}
+
+/// Regression values for `GAME_CODE`, recorded from this implementation.
+///
+/// These are not published vectors, they guard against changes to the key
+/// schedule and rounds described in \[1\].
+///
+/// # Sources
+///
+/// \[1\]: <https://problemkaputt.de/gbatek.htm#dsencryptionbygamecodeidcodekey1>
+#[test]
+fn known_answers() {
+    assert_eq!(
+        encrypt(&Key1::init1(GAME_CODE)),
+        [0x6D, 0xF8, 0xBB, 0x02, 0x77, 0x9C, 0x58, 0x84]
+    );
+    assert_eq!(
+        encrypt(&Key1::init2(GAME_CODE)),
+        [0xE3, 0x02, 0x98, 0xD7, 0xEF, 0x64, 0xFD, 0xBD]
+    );
+    assert_eq!(
+        encrypt(&Key1::init3(GAME_CODE)),
+        [0x07, 0x8C, 0x0B, 0xF1, 0x16, 0xB9, 0xBD, 0x77]
+    );
+
+    let mut block = [0x07, 0x8C, 0x0B, 0xF1, 0x16, 0xB9, 0xBD, 0x77];
+    Key1::init3(GAME_CODE).decrypt_block(&mut block);
+    assert_eq!(&block, b"encryObj");
+}
+
+#[test]
+fn secure_area_known_answers() {
+    // A decrypted secure area has a destroyed ID.
+    let mut secure_area = vec![0u8; 0x800];
+    secure_area[0..8].copy_from_slice(&[0xFF, 0xDE, 0xFF, 0xE7, 0xFF, 0xDE, 0xFF, 0xE7]);
+    let decrypted = secure_area.clone();
+
+    Key1::encrypt_secure_area(&mut secure_area, GAME_CODE);
+    // "encryObj", encrypted with level 3 then level 2.
+    assert_eq!(
+        secure_area[0..8],
+        [0x64, 0xE8, 0x72, 0x75, 0xCF, 0xDA, 0x68, 0xEA]
+    );
+    assert_eq!(
+        secure_area[8..16],
+        [0x9F, 0x42, 0xFF, 0xA2, 0x99, 0xDC, 0xBC, 0xB8]
+    );
+
+    Key1::decrypt_secure_area(&mut secure_area, GAME_CODE);
+    assert_eq!(secure_area, decrypted);
+
+    // Unencrypted code is left unchanged.
+    let mut encrypted = decrypted.clone();
+    Key1::encrypt_secure_area(&mut encrypted, GAME_CODE);
+    assert!(Key1::try_decrypt_secure_area(&mut encrypted, GAME_CODE));
+    assert_eq!(encrypted, decrypted);
+
+    let mut code: Vec<u8> = (0..0x800).map(|i| (i * 7) as u8).collect();
+    let unencrypted = code.clone();
+    assert!(!Key1::try_decrypt_secure_area(&mut code, GAME_CODE));
+    assert_eq!(code, unencrypted);
+}
+
+#[test]
+fn reencrypt_round_trip() {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x4000..0x4008].copy_from_slice(&DESTROYED_ID);
+    rom[0x4010..0x4800].fill(0x5A);
+    let decrypted = rom.clone();
+
+    assert!(reencrypt_secure_area(&mut rom, GAME_CODE, 0x4000));
+    assert_ne!(rom, decrypted);
+
+    // The ID is encrypted with level 3 then level 2.
+    let mut id: [u8; 8] = rom[0x4000..0x4008].try_into().unwrap();
+    Key1::init2(GAME_CODE).decrypt_block(&mut id);
+    Key1::init3(GAME_CODE).decrypt_block(&mut id);
+    assert_eq!(&id, b"encryObj");
+
+    Key1::decrypt_secure_area(&mut rom[0x4000..], GAME_CODE);
+    assert_eq!(rom, decrypted);
+
+    // Already encrypted.
+    Key1::encrypt_secure_area(&mut rom[0x4000..], GAME_CODE);
+    let encrypted = rom.clone();
+    assert!(!reencrypt_secure_area(&mut rom, GAME_CODE, 0x4000));
+    assert_eq!(rom, encrypted);
+
+    // No secure area, or past the end of the ROM.
+    let mut rom = decrypted.clone();
+    assert!(!reencrypt_secure_area(&mut rom, GAME_CODE, 0x200));
+    assert!(!reencrypt_secure_area(
+        &mut rom[..0x6000],
+        GAME_CODE,
+        0x4000
+    ));
+    assert_eq!(rom, decrypted);
+
+    // Fully destroyed.
+    let mut rom = vec![0u8; 0x8000];
+    for chunk in rom[0x4000..0x4800].chunks_exact_mut(8) {
+        chunk.copy_from_slice(&DESTROYED_ID);
+    }
+    let destroyed = rom.clone();
+    assert!(!reencrypt_secure_area(&mut rom, GAME_CODE, 0x4000));
+    assert_eq!(rom, destroyed);
+}
+
+#[cfg(feature = "hashes")]
+#[test]
+fn decrypted_sha1() {
+    use common::util::sha1::sha1;
+    use rom::nds::NdsRom;
+
+    use crate::util::RomBuilder;
+
+    // A decrypted dump, with a destroyed secure area ID.
+    let decrypted = RomBuilder::new()
+        .header(|header| header[0x0C..0x10].copy_from_slice(&GAME_CODE.to_le_bytes()))
+        .secure_area(|secure_area| {
+            secure_area[0x00..0x08].copy_from_slice(&DESTROYED_ID);
+            secure_area[0x10..0x800].fill(0x5A);
+        })
+        .build();
+
+    // Decrypted ROMs are re-encrypted when loaded.
+    let rom = NdsRom::load(&decrypted).unwrap();
+    assert_ne!(rom.as_bytes(), &decrypted[..]);
+    assert_eq!(rom.decrypted_sha1(), sha1(&decrypted));
+
+    // A raw ID is hashed as destroyed.
+    let mut raw = decrypted.clone();
+    raw[0x4000..0x4008].copy_from_slice(b"encryObj");
+    let rom = NdsRom::load(&raw).unwrap();
+    assert_eq!(rom.decrypted_sha1(), sha1(&decrypted));
+
+    // Unencrypted homebrew code at `0x4000` is hashed as is.
+    let homebrew = RomBuilder::new()
+        .header(|header| header[0x0C..0x10].copy_from_slice(b"AXYE"))
+        .secure_area(|secure_area| {
+            for (i, b) in secure_area.iter_mut().enumerate() {
+                *b = (i * 13 + 1) as u8;
+            }
+        })
+        .build();
+    let rom = NdsRom::load(&homebrew).unwrap();
+    assert_eq!(rom.decrypted_sha1(), sha1(&homebrew));
+}
+
+#[cfg(feature = "hashes")]
+#[test]
+fn verify_against_decrypted() {
+    use common::util::crc::crc32;
+    use common::util::sha1::sha1;
+    use rom::nds::{BannerLanguage, DatEntry, DatImage, NdsRom};
+
+    use crate::util::RomBuilder;
+
+    // A decrypted dump, with a destroyed secure area ID.
+    let decrypted = RomBuilder::new()
+        .header(|header| header[0x0C..0x10].copy_from_slice(&GAME_CODE.to_le_bytes()))
+        .secure_area(|secure_area| {
+            secure_area[0x00..0x08].copy_from_slice(&DESTROYED_ID);
+            secure_area[0x10..0x800].fill(0x5A);
+        })
+        .build();
+    let entry = |bytes: &[u8]| DatEntry {
+        size: bytes.len() as u64,
+        crc32: !crc32(bytes),
+        md5: None,
+        sha1: Some(sha1(bytes)),
+    };
+
+    // A decrypted dump matches a decrypted DAT, but not an encrypted one.
+    let rom = NdsRom::load(&decrypted).unwrap();
+    assert!(rom
+        .verify_against(&entry(&decrypted), DatImage::Decrypted)
+        .is_match());
+    assert!(!rom
+        .verify_against(&entry(&decrypted), DatImage::Encrypted)
+        .is_match());
+
+    let encrypted = rom.as_bytes().to_vec();
+    assert!(rom
+        .verify_against(&entry(&encrypted), DatImage::Encrypted)
+        .is_match());
+
+    // The cache is cleared when the secure area is edited.
+    let mut rom = rom;
+    rom.secure_area_mut().unwrap()[0x100] ^= 1;
+    assert!(!rom
+        .verify_against(&entry(&encrypted), DatImage::Encrypted)
+        .is_match());
+
+    // ... and when a file or the banner is edited.
+    let image = RomBuilder::new()
+        .file("a.bin", b"aaaa")
+        .banner(0x0001, |_| {})
+        .build();
+    let mut rom = NdsRom::load(&image).unwrap();
+    let original = entry(&rom.as_bytes()[..image.len()]);
+    assert!(rom
+        .verify_against(&original, DatImage::Encrypted)
+        .is_match());
+
+    rom.replace_file("a.bin", b"bbbb").unwrap();
+    assert!(!rom
+        .verify_against(&original, DatImage::Encrypted)
+        .is_match());
+    let replaced = entry(&rom.as_bytes()[..image.len()]);
+    assert!(rom
+        .verify_against(&replaced, DatImage::Encrypted)
+        .is_match());
+
+    rom.set_banner_title(BannerLanguage::English, "Title")
+        .unwrap();
+    assert!(!rom
+        .verify_against(&replaced, DatImage::Encrypted)
+        .is_match());
+}
@@ -0,0 +1,44 @@
+use rom::nds::{NdsHeader, NdsRom};
+
+mod util;
+
+use util::RomBuilder;
+
+/// Builds a header with a valid logo checksum, and the given used size.
+fn build_rom(title: &[u8], used_size: u32) -> Vec<u8> {
+    RomBuilder::new()
+        .header(|header| {
+            header[..title.len()].copy_from_slice(title);
+            header[0x80..0x84].copy_from_slice(&used_size.to_le_bytes());
+            header[0x15C..0x15E].copy_from_slice(&0xCF56u16.to_le_bytes());
+        })
+        .build()
+}
+
+#[test]
+fn load_all() {
+    // A ROM with another ROM embedded within its used size.
+    let mut image = build_rom(b"GAME A", 0x400);
+    image.extend_from_slice(&build_rom(b"CHILD", 0x200));
+    image.extend_from_slice(&[0; 0x200]);
+    image.extend_from_slice(&build_rom(b"GAME B", 0x200));
+    image.extend_from_slice(&[0xFF; 0x100]);
+
+    let roms = NdsRom::load_all(&image);
+    let found = roms
+        .iter()
+        .map(|(offset, rom)| (*offset, rom.header.game_title.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        [(0x000, "GAME A".to_owned()), (0x600, "GAME B".to_owned())]
+    );
+
+    // The header checksum must be valid.
+    let mut image = build_rom(b"GAME A", 0x200);
+    image[0x15E] ^= 0xFF;
+    assert!(NdsRom::load_all(&image).is_empty());
+
+    assert!(NdsRom::load_all(&[0; 0x1000]).is_empty());
+    assert!(NdsRom::load_all(&[0; NdsHeader::SIZE - 1]).is_empty());
+}
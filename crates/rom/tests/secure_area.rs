@@ -0,0 +1,50 @@
+use rom::nds::encrypt::SecureAreaId;
+use rom::nds::{NdsRom, RegionKind};
+
+/// Builds a ROM with a secure area, starting with `id`.
+fn build_rom(id: &[u8; 8]) -> Vec<u8> {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x0C..0x10].copy_from_slice(b"ABCE");
+    rom[0x20..0x24].copy_from_slice(&0x4000u32.to_le_bytes());
+    rom[0x4000..0x4008].copy_from_slice(id);
+    rom
+}
+
+#[test]
+fn secure_area_id() {
+    let rom = NdsRom::load(&build_rom(b"encryObj")).unwrap();
+    assert_eq!(rom.secure_area_id(), Some(SecureAreaId::Raw));
+
+    let rom = NdsRom::load(&build_rom(&[0x12; 8])).unwrap();
+    assert_eq!(rom.secure_area_id(), Some(SecureAreaId::Encrypted));
+}
+
+#[test]
+fn destroyed_id_is_re_encrypted() {
+    let id = [0xFF, 0xDE, 0xFF, 0xE7, 0xFF, 0xDE, 0xFF, 0xE7];
+    assert_eq!(SecureAreaId::of(&id), SecureAreaId::Destroyed);
+
+    let rom = NdsRom::load(&build_rom(&id)).unwrap();
+    assert_eq!(rom.secure_area_id(), Some(SecureAreaId::Encrypted));
+}
+
+#[test]
+fn no_secure_area() {
+    let rom = NdsRom::load(include_bytes!("../../../demo/TinyFB.nds")).unwrap();
+    assert_eq!(rom.secure_area_id(), None);
+}
+
+#[test]
+fn region_of_secure_area() {
+    let mut rom = build_rom(b"encryObj");
+    // ARM9 boot code past the secure area.
+    rom[0x2C..0x30].copy_from_slice(&0x4010u32.to_le_bytes());
+    rom.resize(0x8010, 0);
+    let rom = NdsRom::load(&rom).unwrap();
+
+    assert_eq!(rom.region_of(0x100), Some(RegionKind::Header));
+    assert_eq!(rom.region_of(0x4000), Some(RegionKind::SecureArea));
+    assert_eq!(rom.region_of(0x7FFF), Some(RegionKind::SecureArea));
+    assert_eq!(rom.region_of(0x8000), Some(RegionKind::Arm9));
+    assert_eq!(rom.region_of(0x8010), Some(RegionKind::Padding));
+}
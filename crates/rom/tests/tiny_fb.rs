@@ -1,4 +1,15 @@
-use rom::nds::NdsRom;
+use std::borrow::Cow;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+
+use common::util::crc::crc32;
+use common::util::sha1::sha1;
+
+use rom::nds::encrypt::Key1;
+use rom::nds::{
+    BootFlow, CartPhase, ClockRate, HeaderEditError, HeaderError, IssueKind, LoadDiagnostic,
+    NdsHeader, NdsRegionLock, NdsRom, OpenError, RamAddress, RomError, RomOffset, RomSummary,
+    Severity, SramKind, VerifyIssue, MAX_ROM_SIZE, NINTENDO_LOGO,
+};
 
 const TINY_FB: &[u8] = include_bytes!("../../../demo/TinyFB.nds");
 
@@ -13,4 +24,891 @@ fn read_rom() {
     assert_eq!(header.compute_header_crc16(), 0x908E);
 
     assert!(rom.banner.is_none());
+    assert_eq!(rom.banner_version(), None);
+    assert!(!rom.has_animated_icon());
+    assert_eq!(rom.display_name(), "NDS.TinyFB");
+}
+
+#[test]
+fn command_settings() {
+    let rom = NdsRom::load(TINY_FB).unwrap();
+
+    let header = &rom.header;
+    assert_eq!(header.secure_area_delay_ms(), 0.0);
+
+    let normal = header.normal_command_settings();
+    assert_eq!(normal.gap1_length, 0);
+    assert_eq!(normal.gap2_length, 0x18);
+    assert!(normal.key2_data);
+    assert!(normal.se);
+    assert!(normal.key2_command);
+    assert_eq!(normal.clock_rate, ClockRate::Fast);
+    assert!(!normal.gap_clocks);
+    assert_eq!(normal.to_bits(), header.normal_command_settings);
+
+    let key1 = header.key1_command_settings();
+    assert_eq!(key1.gap1_length, 0x08F8);
+    assert_eq!(key1.gap2_length, 0x18);
+    assert!(!key1.key2_data);
+    assert!(!key1.key2_command);
+    assert_eq!(key1.to_bits(), header.key1_command_settings);
+
+    assert_eq!(normal.to_string(), "6.7MHz, gap1 0x0000, gap2 0x18, KEY2");
+    assert_eq!(key1.to_string(), "6.7MHz, gap1 0x08F8, gap2 0x18");
+}
+
+#[test]
+fn banner_out_of_bounds() {
+    let mut bytes = TINY_FB.to_vec();
+    bytes[0x68..0x6C].copy_from_slice(&0x100u32.to_le_bytes());
+
+    let err = NdsRom::load(&bytes).unwrap_err();
+    assert_eq!(err, RomError::BannerOutOfBounds { offset: 0x100 });
+}
+
+#[test]
+fn clone_rom() {
+    let rom = NdsRom::load(TINY_FB).unwrap();
+
+    let mut patched = rom.clone();
+    patched.rom[0] = b'X';
+
+    assert_eq!(rom.rom[0], b'N');
+    assert_eq!(patched.header.game_title, rom.header.game_title);
+}
+
+#[test]
+fn multiboot() {
+    let rom = NdsRom::load(TINY_FB).unwrap();
+    assert!(!rom.header.is_multiboot());
+
+    let mut bytes = TINY_FB.to_vec();
+    bytes[0x0C..0x10].copy_from_slice(b"ABCE");
+    bytes[0x20..0x24].copy_from_slice(&0x200u32.to_le_bytes());
+
+    let rom = NdsRom::load(&bytes).unwrap();
+    assert!(rom.header.is_multiboot());
+}
+
+#[test]
+fn from_reader() {
+    let rom = NdsRom::from_reader(Cursor::new(TINY_FB)).unwrap();
+
+    assert_eq!(rom.header.game_title, "NDS.TinyFB");
+    assert_eq!(rom.rom.len(), NdsHeader::SIZE);
+}
+
+#[test]
+fn from_reader_past_end() {
+    let mut reader = Cursor::new(TINY_FB);
+    reader.set_position(TINY_FB.len() as u64 + 0x1000);
+
+    let err = NdsRom::from_reader(reader).unwrap_err();
+    assert!(matches!(
+        err,
+        OpenError::Rom(RomError::TooSmall { size: 0 })
+    ));
+}
+
+#[test]
+fn from_reader_too_large() {
+    /// A reader reporting an oversized stream, without any data.
+    struct Oversized(u64);
+
+    impl Read for Oversized {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Ok(0)
+        }
+    }
+
+    impl Seek for Oversized {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            self.0 = match pos {
+                SeekFrom::Start(n) => n,
+                SeekFrom::End(n) => (MAX_ROM_SIZE as u64 + 1).wrapping_add(n as u64),
+                SeekFrom::Current(n) => self.0.wrapping_add(n as u64),
+            };
+            Ok(self.0)
+        }
+    }
+
+    let err = NdsRom::from_reader(Oversized(0)).unwrap_err();
+    assert!(matches!(
+        err,
+        OpenError::Rom(RomError::TooLarge { size }) if size == MAX_ROM_SIZE as u64 + 1
+    ));
+}
+
+#[test]
+fn fit_device_capacity() {
+    let rom = NdsRom::load(TINY_FB).unwrap();
+    let mut header = rom.header;
+
+    header.fit_device_capacity(128 * 1024);
+    assert_eq!(header.device_capacity, 0);
+
+    header.fit_device_capacity(128 * 1024 + 1);
+    assert_eq!(header.device_capacity, 1);
+    assert_eq!(header.rom_size, 128 * 1024 + 1);
+    assert_eq!(header.used_rom_size(), 128 * 1024 + 1);
+    assert_eq!(header.device_capacity_bytes(), 256 * 1024);
+
+    header.fit_device_capacity(0x2000000);
+    assert_eq!(header.device_capacity, 8);
+    assert_eq!(header.device_capacity_bytes(), 0x2000000);
+    assert_eq!(header.header_size, 0x1A0);
+
+    header.fit_device_capacity(u32::MAX as usize);
+    assert_eq!(header.device_capacity, 15);
+    assert_eq!(header.checked_device_capacity_bytes(), Some(1 << 32));
+
+    // The stored size saturates.
+    header.fit_device_capacity(u32::MAX as usize + 1);
+    assert_eq!(header.device_capacity, 15);
+    assert_eq!(header.rom_size, u32::MAX);
+
+    // Corrupt capacities overflow.
+    header.device_capacity = 46;
+    assert_eq!(header.checked_device_capacity_bytes(), Some(1 << 63));
+    header.device_capacity = 47;
+    assert_eq!(header.checked_device_capacity_bytes(), None);
+    assert_eq!(header.device_capacity_bytes(), usize::MAX);
+    header.device_capacity = 0xFF;
+    assert_eq!(header.checked_device_capacity_bytes(), None);
+}
+
+#[test]
+fn const_header() {
+    const fn pad(bytes: &[u8]) -> [u8; NdsHeader::SIZE] {
+        let mut buf = [0u8; NdsHeader::SIZE];
+        let mut i = 0;
+        while i < bytes.len() {
+            buf[i] = bytes[i];
+            i += 1;
+        }
+        buf
+    }
+
+    const HEADER: NdsHeader = NdsHeader::from_bytes_const(&pad(TINY_FB));
+    const ARM9_SIZE: u32 = HEADER.arm9_size;
+
+    assert_eq!(ARM9_SIZE, 0x44);
+    assert_eq!(HEADER.game_title, "NDS.TinyFB");
+}
+
+#[test]
+fn too_small() {
+    let err = NdsRom::load(&TINY_FB[..10]).unwrap_err();
+    assert_eq!(err, RomError::TooSmall { size: 10 });
+
+    let err = NdsRom::load(&TINY_FB[..(NdsHeader::MIN_SIZE - 1)]).unwrap_err();
+    assert_eq!(
+        err,
+        RomError::TooSmall {
+            size: NdsHeader::MIN_SIZE - 1
+        }
+    );
+}
+
+#[test]
+fn open_exact() {
+    let path = std::env::temp_dir().join(format!("rsds-{}-exact.nds", std::process::id()));
+
+    let mut bytes = TINY_FB.to_vec();
+    bytes.resize(0x300, 0xFF);
+    std::fs::write(&path, &bytes).unwrap();
+    let exact = NdsRom::open_exact(&path).unwrap();
+    let padded = NdsRom::open(&path).unwrap();
+
+    // The header must not be truncated.
+    std::fs::write(&path, TINY_FB).unwrap();
+    let err = NdsRom::open_exact(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(exact.rom.len(), 0x300);
+    assert_eq!(padded.rom.len(), 0x400);
+    assert_eq!(exact.header.game_title, padded.header.game_title);
+    assert!(matches!(
+        err,
+        OpenError::Rom(RomError::TooSmall { size: 0x160 })
+    ));
+}
+
+#[test]
+fn secure_area_out_of_bounds() {
+    let mut rom = TINY_FB.to_vec();
+    // ARM9 ROM offset.
+    rom[0x20..0x24].copy_from_slice(&0x4000u32.to_le_bytes());
+
+    let err = NdsRom::load(&rom).unwrap_err();
+    assert_eq!(err, RomError::SecureAreaOutOfBounds { offset: 0x4000 });
+}
+
+#[test]
+fn logo_is_genuine() {
+    let rom = NdsRom::load(TINY_FB).unwrap();
+    assert!(!rom.header.logo_is_genuine());
+
+    let mut bytes = TINY_FB.to_vec();
+    bytes[0xC0..0x15C].copy_from_slice(&NINTENDO_LOGO);
+
+    // The logo is genuine, regardless of the stored checksum.
+    let rom = NdsRom::load(&bytes).unwrap();
+    assert!(rom.header.logo_is_genuine());
+    assert_eq!(rom.header.compute_logo_crc16(), 0xCF56);
+    assert_ne!(rom.header.nintendo_logo_crc16, 0xCF56);
+}
+
+#[test]
+fn into_inner() {
+    let rom = NdsRom::load(TINY_FB).unwrap();
+    assert_eq!(&rom.as_bytes()[..TINY_FB.len()], TINY_FB);
+
+    let bytes = rom.into_inner();
+    assert_eq!(bytes.len(), NdsHeader::SIZE);
+    assert_eq!(&bytes[..TINY_FB.len()], TINY_FB);
+    assert!(bytes[TINY_FB.len()..].iter().all(|&b| b == 0));
+}
+
+#[test]
+fn verify() {
+    let rom = NdsRom::load(TINY_FB).unwrap();
+
+    let issues = rom.verify();
+    assert_eq!(
+        issues,
+        [
+            VerifyIssue {
+                severity: Severity::Error,
+                kind: IssueKind::Checksum,
+                field: "header_crc16",
+                expected: 0x908E,
+                actual: 0xEB7B,
+            },
+            VerifyIssue {
+                severity: Severity::Error,
+                kind: IssueKind::Checksum,
+                field: "nintendo_logo_crc16",
+                expected: 0xCF56,
+                actual: 0x9E1A,
+            },
+            // Homebrew doesn't need a genuine logo.
+            VerifyIssue {
+                severity: Severity::Warning,
+                kind: IssueKind::Differs,
+                field: "nintendo_logo",
+                expected: 156,
+                actual: 0,
+            },
+        ]
+    );
+    assert_eq!(
+        issues[0].to_string(),
+        "error: header_crc16: checksum 0xEB7B does not match 0x908E"
+    );
+    assert_eq!(
+        issues[2].to_string(),
+        "warning: nintendo_logo: differs at offset 0x0"
+    );
+}
+
+#[test]
+fn verify_tampered_logo() {
+    let mut bytes = TINY_FB.to_vec();
+    bytes[0xC0..0x15C].copy_from_slice(&NINTENDO_LOGO);
+    bytes[0xD0] ^= 0xFF;
+    bytes[0x15C..0x15E].copy_from_slice(&0xCF56u16.to_le_bytes());
+    let rom = NdsRom::load(&bytes).unwrap();
+
+    // The stored checksum is correct, but the logo itself is not.
+    let logo_issues: Vec<_> = rom
+        .verify()
+        .into_iter()
+        .filter(|issue| issue.field.starts_with("nintendo_logo"))
+        .collect();
+    assert_eq!(
+        logo_issues,
+        [VerifyIssue {
+            severity: Severity::Warning,
+            kind: IssueKind::Differs,
+            field: "nintendo_logo",
+            expected: 156,
+            actual: 0x10,
+        }]
+    );
+}
+
+#[test]
+fn verify_out_of_bounds() {
+    let mut rom = TINY_FB.to_vec();
+    // ARM7 size.
+    rom[0x3C..0x40].copy_from_slice(&0x1000u32.to_le_bytes());
+    let rom = NdsRom::load(&rom).unwrap();
+
+    let issue = rom
+        .verify()
+        .into_iter()
+        .find(|issue| issue.kind == IssueKind::OutOfBounds)
+        .unwrap();
+    assert_eq!(issue.field, "arm7_rom_offset");
+    assert_eq!(issue.expected, TINY_FB.len() as u64);
+    assert_eq!(issue.actual, rom.header.arm7_rom_offset as u64 + 0x1000);
+}
+
+#[test]
+fn with_header() {
+    let mut rom = NdsRom::load(TINY_FB).unwrap();
+    rom.with_header(|header| header.rom_version = 0x01);
+
+    assert_eq!(rom.header.rom_version, 0x01);
+    assert_eq!(rom.rom[0x01E], 0x01);
+    assert_eq!(rom.header.header_crc16, rom.header.compute_header_crc16());
+
+    // The ROM data is in sync with the header.
+    let header = NdsHeader::from_bytes_const(rom.rom[..NdsHeader::SIZE].try_into().unwrap());
+    assert_eq!(header.to_bytes(), rom.header.to_bytes());
+    assert!(rom
+        .verify()
+        .iter()
+        .all(|issue| issue.field != "header_crc16"));
+}
+
+#[test]
+fn reparse_header() {
+    let mut rom = NdsRom::load(TINY_FB).unwrap();
+    rom.rom[0x01E] = 0x02;
+    assert_eq!(rom.header.rom_version, 0x00);
+
+    rom.reparse_header();
+    assert_eq!(rom.header.rom_version, 0x02);
+    assert!(rom.dsi_header.is_none());
+
+    // The DSi header must be within the ROM data, not the padding.
+    let mut bytes = TINY_FB.to_vec();
+    bytes.resize(0xFF0, 0);
+    let mut rom = NdsRom::load(&bytes).unwrap();
+    assert_eq!(rom.rom.len(), 0x1000);
+    rom.rom[0x012] = 0x02;
+    rom.reparse_header();
+    assert!(rom.is_dsi());
+    assert!(rom.dsi_header.is_none());
+}
+
+#[test]
+fn checksums() {
+    let mut rom = NdsRom::load(TINY_FB).unwrap();
+
+    let checksums = *rom.checksums();
+    assert_eq!(checksums.header, 0x908E);
+    assert_eq!(checksums.logo, 0x9E1A);
+    assert_eq!(checksums.secure_area, None);
+
+    // Editing the header invalidates the cache.
+    rom.with_header(|header| header.rom_version = 0x01);
+    assert_eq!(rom.checksums().header, rom.header.compute_header_crc16());
+    assert_ne!(rom.checksums().header, checksums.header);
+}
+
+#[test]
+fn boot_flow() {
+    let mut rom = NdsRom::load(TINY_FB).unwrap();
+    assert!(rom.header.is_autostart());
+    assert_eq!(
+        rom.header.boot_flow(),
+        BootFlow {
+            skip_health_safety: true,
+            skip_boot_menu: true,
+            ignore_start_button: true,
+        }
+    );
+
+    rom.header.autostart = 0x00;
+    assert!(!rom.header.is_autostart());
+    assert_eq!(rom.header.boot_flow(), BootFlow::default());
+}
+
+#[test]
+fn header_setters() {
+    let mut header = NdsRom::load(TINY_FB).unwrap().header;
+    header.set_arm9_rom_offset(0x0000_4000);
+    header.set_secure_area_delay(0x0D7E);
+    header.set_secure_area_disable(0x0123_4567_89AB_CDEF);
+
+    let bytes = header.to_bytes();
+    assert_eq!(bytes[0x20..0x24], [0x00, 0x40, 0x00, 0x00]);
+    assert_eq!(bytes[0x6E..0x70], [0x7E, 0x0D]);
+    assert_eq!(
+        bytes[0x78..0x80],
+        [0xEF, 0xCD, 0xAB, 0x89, 0x67, 0x45, 0x23, 0x01]
+    );
+
+    let header = NdsHeader::from_bytes_const(&bytes);
+    assert_eq!(header.arm9_rom_offset, 0x4000);
+}
+
+#[test]
+fn secure_area_config() {
+    let mut header = NdsRom::load(TINY_FB).unwrap().header;
+    header.set_secure_area_delay(0x051E);
+
+    let config = header.secure_area_config();
+    assert_eq!(config.crc16, header.secure_area_crc16);
+    assert_eq!(config.delay_ms.round(), 10.0);
+    assert!(!config.disabled);
+
+    let mut marker = *b"NmMdOnly";
+    Key1::init2(header.game_code()).encrypt_block(&mut marker);
+    header.set_secure_area_disable(u64::from_le_bytes(marker));
+    assert!(header.secure_area_config().disabled);
+
+    // The marker is only valid for the game code it was encrypted with.
+    header.game_code.buf = *b"ABCE";
+    assert!(!header.is_secure_area_disabled());
+}
+
+#[test]
+fn open_with_progress() {
+    const SIZE: u64 = 3 * 1024 * 1024;
+
+    let path = std::env::temp_dir().join(format!("rsds-{}-progress.nds", std::process::id()));
+
+    let mut bytes = TINY_FB.to_vec();
+    bytes.resize(SIZE as usize, 0xFF);
+    std::fs::write(&path, &bytes).unwrap();
+
+    let mut progress = Vec::new();
+    let rom = NdsRom::open_with_progress(&path, |read, total| progress.push((read, total)));
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(rom.unwrap().header.game_title, "NDS.TinyFB");
+    // Read in chunks, so large files report progress more than once.
+    assert!(progress.len() >= 3);
+    assert!(progress.windows(2).all(|w| w[0].0 < w[1].0));
+    assert!(progress.iter().all(|&(_, total)| total == SIZE));
+    assert_eq!(progress.last(), Some(&(SIZE, SIZE)));
+}
+
+#[test]
+fn open_validated() {
+    let path = std::env::temp_dir().join(format!("rsds-{}-validated.nds", std::process::id()));
+    std::fs::write(&path, TINY_FB).unwrap();
+
+    let result = NdsRom::open_validated(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    // The demo ROM has no valid logo checksum, but is still returned.
+    let (rom, issues) = result.unwrap();
+    assert_eq!(rom.header.game_title, "NDS.TinyFB");
+    assert_eq!(issues, rom.verify());
+    assert!(issues
+        .iter()
+        .any(|issue| issue.field == "nintendo_logo_crc16"));
+
+    let err = NdsRom::open_validated(&path).unwrap_err();
+    assert!(matches!(err, OpenError::Io(_)));
+}
+
+#[test]
+fn open_with_hashes() {
+    const SIZE: usize = 3 * 1024 * 1024 + 7;
+
+    let path = std::env::temp_dir().join(format!("rsds-{}-hashes.nds", std::process::id()));
+
+    let mut bytes = TINY_FB.to_vec();
+    bytes.resize(SIZE, 0xFF);
+    std::fs::write(&path, &bytes).unwrap();
+
+    let result = NdsRom::open_with_hashes(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let (rom, hashes) = result.unwrap();
+    assert_eq!(rom.header.game_title, "NDS.TinyFB");
+    assert_eq!(hashes.crc32, !crc32(&bytes));
+    assert_eq!(hashes.sha1, sha1(&bytes));
+}
+
+#[test]
+fn try_load() {
+    let mut bytes = TINY_FB.to_vec();
+    // Banner offset.
+    bytes[0x68..0x6C].copy_from_slice(&0x100u32.to_le_bytes());
+    // ARM9 ROM offset.
+    bytes[0x20..0x24].copy_from_slice(&0x4000u32.to_le_bytes());
+
+    let (rom, diagnostics) = NdsRom::try_load(&bytes);
+    let rom = rom.unwrap();
+    assert_eq!(rom.header.game_title, "NDS.TinyFB");
+    assert!(rom.banner.is_none());
+
+    assert_eq!(
+        diagnostics[..2],
+        [
+            LoadDiagnostic::Error(RomError::SecureAreaOutOfBounds { offset: 0x4000 }),
+            LoadDiagnostic::Error(RomError::BannerOutOfBounds { offset: 0x100 }),
+        ]
+    );
+    // The header checksum no longer matches.
+    assert!(diagnostics.iter().any(|diagnostic| matches!(
+        diagnostic,
+        LoadDiagnostic::Issue(VerifyIssue {
+            field: "header_crc16",
+            ..
+        })
+    )));
+
+    let (rom, diagnostics) = NdsRom::try_load(&TINY_FB[..0x100]);
+    assert!(rom.is_none());
+    assert_eq!(
+        diagnostics,
+        [LoadDiagnostic::Error(RomError::TooSmall { size: 0x100 })]
+    );
+}
+
+#[test]
+fn blocks() {
+    let rom = NdsRom::load(TINY_FB).unwrap();
+    let len = TINY_FB.len();
+    assert!(rom.as_bytes().len() > len);
+
+    let blocks = rom.blocks(0x80).collect::<Vec<_>>();
+    assert_eq!(blocks.len(), len.div_ceil(0x80));
+    assert_eq!(blocks[1].0, 0x80);
+    assert_eq!(blocks[1].1, &rom.as_bytes()[0x80..0x100]);
+
+    // The zero padding added when loading is not ROM data, and the final block
+    // is partial.
+    let blocks = rom.blocks(0x180).collect::<Vec<_>>();
+    let (addr, last) = blocks.last().unwrap();
+    assert_eq!(*addr as usize + last.len(), len);
+    assert_eq!(*last, &TINY_FB[*addr as usize..]);
+}
+
+#[test]
+fn header_ranges() {
+    let mut header = NdsRom::load(TINY_FB).unwrap().header;
+    header.set_arm9_rom_offset(0x200);
+    header.set_arm9_size(0x1234);
+    header.set_fat_offset(0x3000);
+    header.set_fat_size(0x20);
+
+    assert_eq!(header.arm9_rom_range(), 0x200..0x1434);
+    assert_eq!(header.fat_range(), 0x3000..0x3020);
+    assert_eq!(header.banner_range(), None);
+
+    header.set_banner_offset(0x1000);
+    assert_eq!(header.banner_range(), Some(0x1000..0x33C0));
+}
+
+#[test]
+fn field_map() {
+    let map = NdsHeader::field_map();
+    assert_eq!(map[0], (0x000, "game_title", 12));
+    assert!(map.contains(&(0x078, "secure_area_disable", 8)));
+
+    // The fields cover the whole header, without gaps.
+    let end = map.iter().try_fold(0, |end, &(offset, _, size)| {
+        (offset == end).then_some(offset + size)
+    });
+    assert_eq!(end, Some(NdsHeader::SIZE));
+}
+
+#[test]
+fn overdump_info() {
+    let load = |bytes: &[u8], rom_size: u32| {
+        let mut bytes = bytes.to_vec();
+        bytes[0x80..0x84].copy_from_slice(&rom_size.to_le_bytes());
+        NdsRom::load(&bytes).unwrap().overdump_info()
+    };
+
+    // The used size of the demo ROM is unknown.
+    assert_eq!(NdsRom::load(TINY_FB).unwrap().overdump_info(), None);
+    assert_eq!(load(TINY_FB, 0x160), None);
+
+    let info = load(TINY_FB, 0x100).unwrap();
+    assert_eq!((info.used_size, info.trailing_size), (0x100, 0x60));
+    assert!(!info.is_padding);
+
+    let mut padded = TINY_FB.to_vec();
+    padded.resize(0x1A0, 0xFF);
+    let info = load(&padded, 0x160).unwrap();
+    assert_eq!(info.trailing_size, 0x40);
+    assert!(info.is_padding);
+}
+
+#[test]
+fn summary() {
+    let rom = NdsRom::load(TINY_FB).unwrap();
+    let summary = RomSummary::from(&rom);
+
+    assert_eq!(summary.game_code, rom.header.game_code);
+    assert_eq!(summary.title, "NDS.TinyFB");
+    assert_eq!(summary.region, None);
+    assert_eq!(summary.sram_kind, SramKind::None);
+    assert_eq!(summary.chip_id, rom.chip_id);
+    assert_eq!(summary.size, TINY_FB.len());
+}
+
+#[test]
+fn field_by_offset() {
+    let header = NdsRom::load(TINY_FB).unwrap().header;
+
+    assert_eq!(header.get_u32(0x00C), Some(header.game_code()));
+    assert_eq!(header.get_u32(0x020), Some(header.arm9_rom_offset));
+    assert_eq!(header.get_u16(0x15E), Some(header.header_crc16));
+    assert_eq!(header.get_u8(0x014), Some(header.device_capacity));
+
+    assert_eq!(header.get_u8(NdsHeader::SIZE - 1), Some(0));
+    assert_eq!(header.get_u8(NdsHeader::SIZE), None);
+    assert_eq!(header.get_u32(NdsHeader::SIZE - 2), None);
+    assert_eq!(header.get_u32(usize::MAX), None);
+}
+
+#[test]
+fn parse_header() {
+    let rom = NdsRom::load(TINY_FB).unwrap();
+
+    let header = NdsHeader::parse(&rom.rom[..NdsHeader::SIZE]).unwrap();
+    assert_eq!(header.to_bytes(), rom.header.to_bytes());
+    assert_eq!(header.game_title, "NDS.TinyFB");
+
+    assert_eq!(
+        NdsHeader::parse(TINY_FB).unwrap_err(),
+        HeaderError::TooSmall { size: 0x160 }
+    );
+}
+
+#[test]
+fn chip_id_for_phase() {
+    let rom = NdsRom::load(TINY_FB).unwrap();
+
+    for phase in [CartPhase::Unencrypted, CartPhase::Key1, CartPhase::Key2] {
+        assert_eq!(rom.chip_id_for_phase(phase), rom.chip_id);
+    }
+}
+
+#[test]
+fn read_at() {
+    let rom = NdsRom::load(TINY_FB).unwrap();
+    let len = TINY_FB.len();
+    assert!(rom.rom.len() > len);
+
+    assert_eq!(&*rom.read_at(0x0C, 4), b"####");
+    assert!(matches!(rom.read_at(0, len), Cow::Borrowed(_)));
+
+    // The zero padding added when loading is not ROM data.
+    let bytes = rom.read_at(len as u32 - 2, 4);
+    assert_eq!(&*bytes, &[TINY_FB[len - 2], TINY_FB[len - 1], 0xFF, 0xFF]);
+    assert_eq!(&*rom.read_at(len as u32, 2), &[0xFF; 2]);
+    assert_eq!(&*rom.read_at(0x1000_0000, 3), &[0xFF; 3]);
+    assert_eq!(&*rom.read_at(u32::MAX, 2), &[0xFF; 2]);
+}
+
+#[test]
+fn typed_addresses() {
+    let header = NdsRom::load(TINY_FB).unwrap().header;
+
+    assert_eq!(header.arm9_rom_offset(), RomOffset(header.arm9_rom_offset));
+    assert_eq!(
+        header.arm9_entry_address(),
+        RamAddress(header.arm9_entry_address)
+    );
+    assert_eq!(header.banner_offset(), RomOffset(0));
+    assert_eq!(
+        header.arm9_rom_offset().as_usize(),
+        header.arm9_rom_range().start
+    );
+    assert_eq!(RamAddress(0x2000000).to_string(), "0x02000000");
+    assert_eq!(u32::from(RomOffset(0x4000)), 0x4000);
+}
+
+#[cfg(feature = "hashes")]
+#[test]
+fn verify_against() {
+    use common::util::md5::md5;
+    use rom::nds::{DatEntry, DatImage, DatVerdict};
+
+    let rom = NdsRom::load(TINY_FB).unwrap();
+    let mut entry = DatEntry {
+        size: TINY_FB.len() as u64,
+        crc32: !crc32(TINY_FB),
+        md5: Some(md5(TINY_FB)),
+        sha1: None,
+    };
+
+    let verdict = rom.verify_against(&entry, DatImage::Encrypted);
+    assert!(verdict.is_match());
+    assert_eq!(verdict.sha1, None);
+
+    entry.sha1 = Some([0; 20]);
+    entry.size += 1;
+    let verdict = rom.verify_against(&entry, DatImage::Encrypted);
+    assert!(!verdict.is_match());
+    assert_eq!(
+        verdict,
+        DatVerdict {
+            size: false,
+            crc32: true,
+            md5: Some(true),
+            sha1: Some(false),
+        }
+    );
+}
+
+#[test]
+fn shared_between_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<NdsRom>();
+
+    let rom = NdsRom::load(TINY_FB).unwrap().into_arc();
+    let checksums = std::thread::scope(|scope| {
+        let handles = (0..4)
+            .map(|_| scope.spawn(|| *rom.checksums()))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+    assert!(checksums
+        .iter()
+        .all(|checksums| checksums == rom.checksums()));
+}
+
+#[test]
+fn reserved_strings() {
+    // The demo ROM stores code, and a string, in the reserved fields.
+    let header = NdsRom::load(TINY_FB).unwrap().header;
+    assert_eq!(header.reserved_strings(), [(0x0AC, "PASS".to_owned())]);
+
+    let mut bytes = [0; NdsHeader::SIZE];
+    bytes[0x000..0x00A].copy_from_slice(b"NDS.TinyFB");
+    bytes[0x015..0x01C].copy_from_slice(b"v1.2\0ab");
+    bytes[0x098..0x09A].copy_from_slice(b"ab");
+    bytes[0x180..0x18F].copy_from_slice(b"BUILD 1234\x01test");
+    let header = NdsHeader::from_bytes_const(&bytes);
+
+    assert_eq!(&header.reserved1()[..4], b"v1.2");
+    assert_eq!(
+        header.reserved_strings(),
+        [
+            (0x015, "v1.2".to_owned()),
+            (0x180, "BUILD 1234".to_owned()),
+            (0x18B, "test".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn trim() {
+    let load = |bytes: &[u8], rom_size: u32| {
+        let mut bytes = bytes.to_vec();
+        bytes[0x80..0x84].copy_from_slice(&rom_size.to_le_bytes());
+        (bytes.clone(), NdsRom::load(&bytes).unwrap())
+    };
+
+    // The used size of the demo ROM is unknown, the padding is removed.
+    let rom = NdsRom::load(TINY_FB).unwrap();
+    assert_eq!(rom.trim(), TINY_FB);
+
+    let (bytes, rom) = load(TINY_FB, 0x100);
+    assert_eq!(rom.trim(), &bytes[..0x100]);
+
+    // Larger than the ROM data.
+    let (bytes, rom) = load(TINY_FB, 0x1000);
+    assert_eq!(rom.trim(), bytes);
+}
+
+#[test]
+fn secure_area_disable_marker() {
+    // The demo ROM stores code in the field.
+    let rom = NdsRom::load(TINY_FB).unwrap();
+    assert!(!rom.secure_area_disable_marker().unwrap().disabled);
+
+    let mut marker = *b"NmMdOnly";
+    Key1::init2(rom.game_code()).encrypt_block(&mut marker);
+    let mut bytes = TINY_FB.to_vec();
+    bytes[0x078..0x080].copy_from_slice(&marker);
+
+    let rom = NdsRom::load(&bytes).unwrap();
+    let marker = rom.secure_area_disable_marker().unwrap();
+    assert_eq!(&marker.decrypted, b"NmMdOnly");
+    assert!(marker.disabled);
+
+    bytes[0x078..0x080].fill(0);
+    let rom = NdsRom::load(&bytes).unwrap();
+    assert_eq!(rom.secure_area_disable_marker(), None);
+}
+
+#[test]
+fn set_game_title_and_code() {
+    let mut rom = NdsRom::load(TINY_FB).unwrap();
+
+    rom.with_header(|h| {
+        h.set_game_title("PATCHED").unwrap();
+        h.set_game_code("abcE").unwrap();
+    });
+    assert_eq!(&rom.header.game_title.buf, b"PATCHED\0\0\0\0\0");
+    assert_eq!(rom.header.game_code, "ABCE");
+    assert_eq!(&rom.rom[0x000..0x010], b"PATCHED\0\0\0\0\0ABCE");
+
+    // The edited header is valid.
+    let rom = NdsRom::load(&rom.rom).unwrap();
+    assert_eq!(rom.header.header_crc16, rom.header.compute_header_crc16());
+
+    let mut header = rom.header;
+    assert_eq!(
+        header.set_game_title("THIRTEEN CHRS"),
+        Err(HeaderEditError::TooLong {
+            len: 13,
+            capacity: 12
+        })
+    );
+    assert_eq!(
+        header.set_game_title("TAB\tTITLE"),
+        Err(HeaderEditError::InvalidChar { index: 3 })
+    );
+    assert_eq!(
+        header.set_game_code("ABC"),
+        Err(HeaderEditError::WrongLength {
+            len: 3,
+            expected: 4
+        })
+    );
+    assert_eq!(
+        header.set_game_code("AB\u{e9}"),
+        Err(HeaderEditError::InvalidChar { index: 2 })
+    );
+    // Failed edits leave the fields unchanged.
+    assert_eq!(header.game_title, "PATCHED");
+    assert_eq!(header.game_code, "ABCE");
+
+    header.game_title.buf[0] = b'X';
+    header.fix_crc();
+    assert_eq!(header.header_crc16, header.compute_header_crc16());
+}
+
+#[test]
+fn region_lock() {
+    use NdsRegionLock::*;
+
+    let cases = [
+        (0x00, None, [true, true, true]),
+        (0x40, Some(Korea), [false, true, false]),
+        (0x80, Some(China), [false, false, true]),
+        // China takes priority over Korea.
+        (0xC0, Some(China), [false, false, true]),
+    ];
+    for (flags, locked, runs) in cases {
+        let mut bytes = TINY_FB.to_vec();
+        bytes[0x01D] = flags;
+        let rom = NdsRom::load(&bytes).unwrap();
+
+        assert_eq!(rom.region_locked_to(), locked, "{:#04X}", flags);
+        assert_eq!(
+            [Normal, Korea, China].map(|region| rom.can_run_in_region(region)),
+            runs,
+            "{:#04X}",
+            flags
+        );
+    }
 }
@@ -0,0 +1,245 @@
+//! Synthetic ROM images for tests.
+
+#![allow(dead_code)]
+
+use common::util::crc;
+use rom::nds::{DsiHeader, NdsBanner, NdsHeader, ROOT_DIR_ID};
+
+/// Regions are aligned to this many bytes.
+const ALIGN: usize = 0x20;
+
+/// The ROM address of the secure area.
+pub const SECURE_AREA_OFFSET: usize = 0x4000;
+/// The size of the secure area in bytes.
+pub const SECURE_AREA_SIZE: usize = 0x4000;
+
+/// The secure area ID of a decrypted dump, as destroyed by the BIOS.
+pub const DESTROYED_ID: [u8; 8] = [0xFF, 0xDE, 0xFF, 0xE7, 0xFF, 0xDE, 0xFF, 0xE7];
+
+/// Builds a synthetic ROM image.
+///
+/// The ROM is laid out as follows, with each region aligned to `0x20` bytes:
+///
+/// - header (`0x1000` bytes if the unit code is DSi, otherwise `0x200` bytes)
+/// - secure area at `0x4000`, if any
+/// - file name table (FNT), if there are files
+/// - file allocation table (FAT), if there are files
+/// - file data, in file ID order
+/// - banner, if any
+///
+/// The header checksum is fixed up, the ROM is not padded.
+pub struct RomBuilder {
+    header: Vec<u8>,
+    secure_area: Option<Vec<u8>>,
+    files: Vec<(String, Vec<u8>)>,
+    banner: Option<Vec<u8>>,
+}
+
+struct Dir {
+    name: String,
+    parent: usize,
+    entries: Vec<Node>,
+}
+
+enum Node {
+    File(usize, String),
+    Dir(usize),
+}
+
+fn align(offset: usize) -> usize {
+    (offset + ALIGN - 1) & !(ALIGN - 1)
+}
+
+fn write_u32(rom: &mut [u8], offset: usize, value: u32) {
+    rom[offset..(offset + 4)].copy_from_slice(&value.to_le_bytes());
+}
+
+impl RomBuilder {
+    pub fn new() -> RomBuilder {
+        RomBuilder {
+            header: vec![0u8; DsiHeader::OFFSET + DsiHeader::SIZE],
+            secure_area: None,
+            files: Vec::new(),
+            banner: None,
+        }
+    }
+
+    /// Edits the raw header bytes, including the DSi extended header.
+    pub fn header(mut self, edit: impl FnOnce(&mut [u8])) -> RomBuilder {
+        edit(&mut self.header);
+        self
+    }
+
+    /// Adds a secure area, using `edit` to fill in the secure area bytes, and
+    /// sets the ARM9 ROM offset to point at it.
+    pub fn secure_area(mut self, edit: impl FnOnce(&mut [u8])) -> RomBuilder {
+        let mut secure_area = vec![0u8; SECURE_AREA_SIZE];
+        edit(&mut secure_area);
+        self.secure_area = Some(secure_area);
+        self
+    }
+
+    /// Adds a file at a `/` separated path, creating directories as needed.
+    ///
+    /// File IDs are assigned directory by directory, in the order directories
+    /// are created, then in the order files are added.
+    pub fn file(mut self, path: &str, data: &[u8]) -> RomBuilder {
+        self.files.push((path.to_owned(), data.to_vec()));
+        self
+    }
+
+    /// Adds a banner of the given version, using `edit` to fill in the
+    /// remaining banner bytes.
+    pub fn banner(mut self, version: u16, edit: impl FnOnce(&mut [u8])) -> RomBuilder {
+        let mut banner = vec![0u8; NdsBanner::SIZE];
+        banner[0..2].copy_from_slice(&version.to_le_bytes());
+        edit(&mut banner);
+        self.banner = Some(banner);
+        self
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        let header_size = if self.header[0x012] & 0x02 != 0 {
+            self.header.len()
+        } else {
+            NdsHeader::SIZE
+        };
+        let mut rom = self.header[..header_size].to_vec();
+
+        if let Some(secure_area) = &self.secure_area {
+            rom.resize(SECURE_AREA_OFFSET, 0);
+            rom.extend_from_slice(secure_area);
+
+            write_u32(&mut rom, 0x20, SECURE_AREA_OFFSET as u32);
+        }
+
+        if !self.files.is_empty() {
+            let (fnt, file_ids) = build_fnt(&self.files);
+
+            let mut order = (0..self.files.len()).collect::<Vec<_>>();
+            order.sort_by_key(|&i| file_ids[i]);
+
+            let fnt_offset = align(rom.len());
+            let fat_offset = align(fnt_offset + fnt.len());
+            let mut offset = align(fat_offset + self.files.len() * 8);
+
+            rom.resize(offset, 0);
+            rom[fnt_offset..(fnt_offset + fnt.len())].copy_from_slice(&fnt);
+            for (id, &i) in order.iter().enumerate() {
+                let data = &self.files[i].1;
+                let entry = fat_offset + id * 8;
+                write_u32(&mut rom, entry, offset as u32);
+                write_u32(&mut rom, entry + 4, (offset + data.len()) as u32);
+
+                rom.extend_from_slice(data);
+                offset += data.len();
+            }
+
+            write_u32(&mut rom, 0x40, fnt_offset as u32);
+            write_u32(&mut rom, 0x44, fnt.len() as u32);
+            write_u32(&mut rom, 0x48, fat_offset as u32);
+            write_u32(&mut rom, 0x4C, (self.files.len() * 8) as u32);
+        }
+
+        if let Some(banner) = &self.banner {
+            let banner_offset = align(rom.len());
+            rom.resize(banner_offset, 0);
+            rom.extend_from_slice(banner);
+
+            write_u32(&mut rom, 0x68, banner_offset as u32);
+        }
+
+        let crc = crc::crc16(&rom[..0x15E]);
+        rom[0x15E..0x160].copy_from_slice(&crc.to_le_bytes());
+        rom
+    }
+}
+
+/// Builds a file name table (FNT), returning the FNT and the file ID of each
+/// file.
+fn build_fnt(files: &[(String, Vec<u8>)]) -> (Vec<u8>, Vec<u16>) {
+    let mut dirs = vec![Dir {
+        name: String::new(),
+        parent: 0,
+        entries: Vec::new(),
+    }];
+
+    for (i, (path, _)) in files.iter().enumerate() {
+        let mut names = path
+            .split('/')
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<_>>();
+        let file_name = names.pop().expect("empty file path");
+
+        let mut dir = 0;
+        for name in names {
+            let existing = dirs[dir].entries.iter().find_map(|entry| match *entry {
+                Node::Dir(id) if dirs[id].name == name => Some(id),
+                _ => None,
+            });
+            dir = match existing {
+                Some(id) => id,
+                None => {
+                    dirs.push(Dir {
+                        name: name.to_owned(),
+                        parent: dir,
+                        entries: Vec::new(),
+                    });
+                    let id = dirs.len() - 1;
+                    dirs[dir].entries.push(Node::Dir(id));
+                    id
+                }
+            };
+        }
+        dirs[dir].entries.push(Node::File(i, file_name.to_owned()));
+    }
+
+    let mut file_ids = vec![0u16; files.len()];
+    let mut first_ids = Vec::with_capacity(dirs.len());
+    let mut subtables = Vec::with_capacity(dirs.len());
+    let mut next_id = 0;
+    for dir in &dirs {
+        first_ids.push(next_id);
+
+        let mut subtable = Vec::new();
+        for entry in &dir.entries {
+            match entry {
+                Node::File(i, name) => {
+                    file_ids[*i] = next_id;
+                    next_id += 1;
+
+                    subtable.push(name.len() as u8);
+                    subtable.extend_from_slice(name.as_bytes());
+                }
+                Node::Dir(id) => {
+                    let name = &dirs[*id].name;
+                    subtable.push(0x80 | name.len() as u8);
+                    subtable.extend_from_slice(name.as_bytes());
+                    subtable.extend_from_slice(&(ROOT_DIR_ID + *id as u16).to_le_bytes());
+                }
+            }
+        }
+        subtable.push(0x00);
+        subtables.push(subtable);
+    }
+
+    let mut fnt = Vec::new();
+    let mut offset = dirs.len() * 8;
+    for (id, dir) in dirs.iter().enumerate() {
+        let parent = if id == 0 {
+            dirs.len() as u16
+        } else {
+            ROOT_DIR_ID + dir.parent as u16
+        };
+
+        fnt.extend_from_slice(&(offset as u32).to_le_bytes());
+        fnt.extend_from_slice(&first_ids[id].to_le_bytes());
+        fnt.extend_from_slice(&parent.to_le_bytes());
+        offset += subtables[id].len();
+    }
+    for subtable in subtables {
+        fnt.extend_from_slice(&subtable);
+    }
+
+    (fnt, file_ids)
+}